@@ -12,7 +12,9 @@ fn main() -> anyhow::Result<()> {
         .collect::<Vec<CfdCase<2021>>>()
         .into_par_iter()
         .map(|cfd_case| {
-            let data_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+            let data_path = cfd::Baseline::<2021>::default_path()
+                .unwrap()
+                .join(cfd_case.to_string());
             let mut gir = MonitorsLoader::<2021>::default()
                 .data_path(data_path)
                 .header_filter("GIR")
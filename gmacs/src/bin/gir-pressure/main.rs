@@ -10,7 +10,7 @@ fn main() -> anyhow::Result<()> {
         .nth(25)
         .unwrap();
     let data_file = cfd::CfdDataFile::<2021>::TelescopePressure;
-    for file in data_file.glob(cfd_case)?.iter().last() {
+    for file in data_file.glob(&cfd::Baseline::default(), cfd_case)?.iter().last() {
         let telescope = Telescope::from_path(&file).unwrap();
         // println!("{telescope}");
         // let telescope_mean_pressure = telescope.mean_pressure();
@@ -91,7 +91,7 @@ fn main() -> anyhow::Result<()> {
         .nth(57)
         .unwrap();
     let data_file = cfd::CfdDataFile::<2021>::TelescopePressure;
-    let telescope_pressure_files = data_file.glob(cfd_case)?;
+    let telescope_pressure_files = data_file.glob(&cfd::Baseline::default(), cfd_case)?;
     println!(
         "CFD CASE: {} ({} pressure files)",
         cfd_case,
@@ -211,7 +211,7 @@ fn main() -> anyhow::Result<()> {
         })
         .collect::<Vec<GMACSWindLoads>>();
 
-    let cfd_path = cfd::Baseline::<2021>::default_path();
+    let cfd_path = cfd::Baseline::<2021>::default_path()?;
     let cfd_case_path = cfd_path.join(cfd_case.to_string());
     serde_pickle::to_writer(
         &mut File::create(cfd_case_path.join("gmacs.pkl")).unwrap(),
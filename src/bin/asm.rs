@@ -1,13 +1,13 @@
 //use asm::{pressure, refraction_index};
 //use indicatif::ParallelProgressIterator;
-use parse_monitors::{cfd, cfd::BaselineTrait, pressure::Pressure, FORCE_SAMPLING_FREQUENCY};
+use parse_monitors::{cfd, cfd::BaselineTrait, pressure::Pressure};
 use rayon::prelude::*;
-use std::{env, iter::once, path::Path, time::Instant};
+use std::{env, iter::once, time::Instant};
 
 const R: f64 = 1.2;
 
 fn main() -> anyhow::Result<()> {
-    let duration = 400;
+    let duration = 400f64;
 
     let job_idx = env::var("AWS_BATCH_JOB_ARRAY_INDEX")
         .expect("AWS_BATCH_JOB_ARRAY_INDEX env var missing")
@@ -25,32 +25,18 @@ fn main() -> anyhow::Result<()> {
     let pressure_stats = "m2-es_pressure-stats.csv";
 
     let now = Instant::now();
-    let case_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
-    let files: Vec<_> = cfd::CfdDataFile::<2021>::M2Pressure
-        .glob(cfd_case)
-        .unwrap()
-        .into_iter()
-        .map(|p| p.to_str().unwrap().to_string())
-        .collect();
-    let n_sample = duration * FORCE_SAMPLING_FREQUENCY as usize;
-    let n_skip = if files.len() < n_sample {
-        panic!("Not enough data sample")
-    } else {
-        files.len() - n_sample
-    };
+    let case_path = cfd::Baseline::<2021>::default_path()?.join(cfd_case.to_string());
+    let catalog = cfd::CaseCatalog::new(&cfd::Baseline::default(), cfd_case)?;
+    let files = catalog.last(cfd::CfdDataFile::M2Pressure, duration);
+    match catalog.time_range(cfd::CfdDataFile::M2Pressure) {
+        Some((start, end)) if end - start >= duration => (),
+        _ => panic!("Not enough data sample"),
+    }
 
     let records: Vec<_> = files
-        .into_par_iter()
-        .skip(n_skip)
+        .par_iter()
         //.progress_count(n_files as u64)
-        .map(|file| {
-            let path = Path::new(&file);
-            let stem = Path::new(path.file_stem().unwrap())
-                .file_stem()
-                .unwrap()
-                .to_str()
-                .unwrap();
-            let time = &stem[8..].parse::<f64>().unwrap();
+        .map(|cfd::DataFile { time, path }| {
             let csv_pressure = Pressure::<M12>::decompress(path.to_path_buf()).unwrap();
             let csv_geometry = Pressure::<M12>::decompress(path.with_file_name(geometry)).unwrap();
             let pressures = Pressure::<M12>::load(csv_pressure).unwrap();
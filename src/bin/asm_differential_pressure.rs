@@ -16,7 +16,7 @@ impl Config for geotrans::M1 {
         Ok((
             "M1p.csv.z".to_string(),
             cfd::CfdDataFile::<2021>::M1Pressure
-                .glob(cfd_case)?
+                .glob(&cfd::Baseline::default(), cfd_case)?
                 .map(|p| p.unwrap().to_str().unwrap().to_string())
                 .collect(),
         ))
@@ -27,7 +27,7 @@ impl Config for geotrans::M2 {
         Ok((
             "M2p.csv.z".to_string(),
             cfd::CfdDataFile::<2021>::M2Pressure
-                .glob(cfd_case)?
+                .glob(&cfd::Baseline::default(), cfd_case)?
                 .map(|p| p.unwrap().to_str().unwrap().to_string())
                 .collect(),
         ))
@@ -38,13 +38,14 @@ fn main() -> anyhow::Result<()> {
     type M12 = geotrans::M2;
 
     let progress = Mutex::new(Progress::new());
+    let cfd_root = cfd::Baseline::<2021>::default_path()?;
 
     cfd::Baseline::<2021>::mount()
         .into_iter()
         .collect::<Vec<cfd::CfdCase<2021>>>()
         .into_par_iter()
         .for_each(|cfd_case| {
-            let case_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+            let case_path = cfd_root.join(cfd_case.to_string());
             let (_geometry, files) = M12::configure(cfd_case).unwrap();
 
             let bar: Bar = progress.lock().unwrap().bar(8001, format!("{}", cfd_case));
@@ -4,6 +4,7 @@ use strum::IntoEnumIterator;
 use parse_monitors::{cfd, cfd::BaselineTrait};
 
 fn main() -> anyhow::Result<()> {
+    let cfd_root = cfd::Baseline::<2021>::default_path()?;
     for zenith_angle in cfd::ZenithAngle::iter() {
         let asm_pressure: Vec<_> = cfd::Baseline::<2021>::at_zenith(zenith_angle)
             .into_iter()
@@ -17,8 +18,8 @@ fn main() -> anyhow::Result<()> {
 "#,
                     &cfd_case.to_pretty_string(),
                     &cfd_case.to_string(),
-                    cfd::Baseline::<2021>::path()
-                        .join(&cfd_case.to_string())
+                    cfd_root
+                        .join(cfd_case.to_string())
                         .join("m2_pressure-stats_std_within")
                 )
             })
@@ -39,8 +40,8 @@ fn main() -> anyhow::Result<()> {
 "#,
                     &cfd_case.to_pretty_string(),
                     &cfd_case.to_string(),
-                    cfd::Baseline::<2021>::path()
-                        .join(&cfd_case.to_string())
+                    cfd_root
+                        .join(cfd_case.to_string())
                         .join("m2_temperature-stats_std_within")
                 )
             })
@@ -8,7 +8,7 @@
 //!  - mirror: M1 or M2
 //!  - stats: Mean or Std
 
-use parse_monitors::cfd;
+use parse_monitors::{cfd, cfd::BaselineTrait};
 use plotters::prelude::*;
 use polars::prelude::*;
 use rayon::prelude::*;
@@ -22,7 +22,7 @@ fn main() -> Result<()> {
         .map(|data_path| {
             let mut df = {
                 let filename = format!("{}_temperature-stats_within.csv", mirror);
-                let path = cfd::Baseline::<2021>::path()
+                let path = cfd::Baseline::<2021>::default_path()?
                     .join(data_path.to_string())
                     .join(filename);
                 CsvReader::from_path(path)?
@@ -33,7 +33,7 @@ fn main() -> Result<()> {
             df.sort_in_place("Time [s]", false)?;
 
             let filename = "m2_temperature-stats_std_within.png";
-            let path = cfd::Baseline::<2021>::path()
+            let path = cfd::Baseline::<2021>::default_path()?
                 .join(data_path.to_string())
                 .join(filename);
 
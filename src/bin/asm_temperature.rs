@@ -1,15 +1,13 @@
 //use asm::{pressure, refraction_index};
 use geotrans::{Segment, SegmentTrait, Transform, M2};
-use parse_monitors::{
-    cfd, cfd::BaselineTrait, temperature::Temperature, TEMPERATURE_SAMPLING_FREQUENCY,
-};
+use parse_monitors::{cfd, cfd::BaselineTrait, temperature::Temperature};
 use rayon::prelude::*;
-use std::{env, iter::once, path::Path, time::Instant};
+use std::{env, iter::once, time::Instant};
 
 const R: f64 = 1.2;
 
 fn main() -> anyhow::Result<()> {
-    let duration = 400;
+    let duration = 400f64;
     let pressure_stats = "m2-es_temperature-stats.csv";
     let job_idx = env::var("AWS_BATCH_JOB_ARRAY_INDEX")
         .expect("AWS_BATCH_JOB_ARRAY_INDEX env var missing")
@@ -22,34 +20,14 @@ fn main() -> anyhow::Result<()> {
         .unwrap();
 
     let now = Instant::now();
-    let case_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
-    let files: Vec<_> = cfd::CfdDataFile::<2021>::TemperatureField
-        .glob(cfd_case)
-        .unwrap()
-        .into_iter()
-        .map(|p| p.to_str().unwrap().to_string())
-        .collect();
-    let n_sample = duration * TEMPERATURE_SAMPLING_FREQUENCY as usize;
-    let n_skip = if files.len() < n_sample {
-        0
-    } else {
-        files.len() - n_sample
-    };
-    let pattern = cfd::CfdDataFile::<2021>::TemperatureField.pattern();
+    let case_path = cfd::Baseline::<2021>::default_path()?.join(cfd_case.to_string());
+    let catalog = cfd::CaseCatalog::new(&cfd::Baseline::default(), cfd_case)?;
+    let files = catalog.last(cfd::CfdDataFile::TemperatureField, duration);
 
     let records: anyhow::Result<Vec<_>> = files
-        .into_par_iter()
-        .skip(n_skip)
+        .par_iter()
         //.progress_count(n_sample as u64)
-        .map(|file| {
-            let path = Path::new(&file);
-            let time = Path::new(path.file_stem().unwrap())
-                .file_stem()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .replace(&pattern, "")
-                .parse::<f64>()?;
+        .map(|cfd::DataFile { time, path }| {
             type Data = Vec<f64>;
             let es_temp: Vec<(i32, f64)> = {
                 let (x, y, z, temp): (Data, Data, Data, Data) = {
@@ -108,7 +86,7 @@ fn main() -> anyhow::Result<()> {
                 .iter()
                 .map(|temp| temp.iter().sum::<f64>() / temp.len() as f64)
                 .collect();
-            Ok((time, temperature_mean))
+            Ok((*time, temperature_mean))
         })
         .collect();
 
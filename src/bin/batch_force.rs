@@ -4,7 +4,7 @@
 
 use std::{fs::create_dir, path::Path};
 
-use parse_monitors::{cfd::Baseline, cfd::BaselineTrait, Mirror, Monitors};
+use parse_monitors::{cfd::CaseSelection, Mirror, MonitorGroup, MonitorGroups, Monitors};
 use rayon::prelude::*;
 use structopt::StructOpt;
 
@@ -16,59 +16,29 @@ struct Opt {
     /// Make all the plots
     #[structopt(long)]
     all: bool,
-    /// Make C-Rings force magnitude plot
-    #[structopt(long)]
-    crings: bool,
-    /// Make M1 cell force magnitude plot
-    #[structopt(long)]
-    m1_cell: bool,
-    /// Make upper truss force magnitude plot
-    #[structopt(long)]
-    upper_truss: bool,
-    /// Make lower truss force magnitude plot
-    #[structopt(long)]
-    lower_truss: bool,
-    /// Make top-end force magnitude plot
+    /// Make the force magnitude plot of the given group, see `MonitorGroups::parts` for the default groups
+    #[structopt(short, long)]
+    group: Vec<String>,
+    /// Groups TOML or JSON file, instead of the default groups
     #[structopt(long)]
-    top_end: bool,
+    groups: Option<String>,
     /// Make M1 segments force magnitude plot
     #[structopt(long)]
     m1_segments: bool,
-    /// Make M2 segments force magnitude plot
-    #[structopt(long)]
-    m2_segments: bool,
-    /// Make M1 and M2 baffles force magnitude plot
-    #[structopt(long)]
-    m12_baffles: bool,
-    /// Make M1 inner mirror covers force magnitude plot
-    #[structopt(long)]
-    m1_inner_covers: bool,
-    /// Make M1 outer mirror covers force magnitude plot
-    #[structopt(long)]
-    m1_outer_covers: bool,
-    /// Make GIR force magnitude plot
-    #[structopt(long)]
-    gir: bool,
-    /// Make PFA arms force magnitude plot
-    #[structopt(long)]
-    pfa_arms: bool,
-    /// Make Laser Guide Stars assemblies force magnitude plot
-    #[structopt(long)]
-    lgsa: bool,
-    /// Make platforms and cables force magnitude plot
-    #[structopt(long)]
-    platforms_cables: bool,
     /// Remove linear trends from monitors
     #[structopt(long)]
     detrend: bool,
+    #[structopt(flatten)]
+    selection: CaseSelection,
 }
 
 const CFD_YEAR: u32 = 2021;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opt = Opt::from_args();
-    let cfd_root = Baseline::<CFD_YEAR>::path();
-    let data_paths: Vec<_> = Baseline::<CFD_YEAR>::default()
+    let baseline = opt.selection.baseline::<CFD_YEAR>()?;
+    let cfd_root = baseline.path()?;
+    let data_paths: Vec<_> = baseline
         .into_iter()
         .map(|cfd_case| {
             cfd_root
@@ -81,26 +51,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let n_cases = data_paths.len();
     println!("Found {} CFD cases", n_cases);
 
-    let parts = vec![
-        (opt.crings || opt.all).then(|| Some(("c-ring_parts", "Cring"))),
-        (opt.m1_cell || opt.all).then(|| Some(("m1-cell", "M1cell"))),
-        (opt.upper_truss || opt.all).then(|| Some(("upper-truss", "Tu"))),
-        (opt.lower_truss || opt.all).then(|| Some(("lower-truss", "Tb"))),
-        (opt.top_end || opt.all).then(|| Some(("top-end", "Top"))),
-        (opt.m2_segments || opt.all).then(|| Some(("m2-segments", "M2s"))),
-        (opt.m12_baffles || opt.all).then(|| Some(("m12-baffles", "Baf"))),
-        (opt.m1_outer_covers || opt.all).then(|| Some(("m1-outer-covers", "M1cov[1-6]"))),
-        (opt.m1_inner_covers || opt.all).then(|| Some(("m1-inner-covers", "M1covin[1-6]"))),
-        (opt.gir || opt.all).then(|| Some(("gir", "GIR"))),
-        (opt.pfa_arms || opt.all).then(|| Some(("pfa-arms", "arm"))),
-        (opt.lgsa || opt.all).then(|| Some(("lgs", "LGS"))),
-        (opt.platforms_cables || opt.all).then(|| Some(("platforms-cables", "cable|plat|level"))),
-        opt.m1_segments.then(|| Some(("m1-segments", ""))),
-    ];
+    let groups = match opt.groups {
+        Some(path) => MonitorGroups::from_path(path)?,
+        None => MonitorGroups::parts(),
+    };
+    let parts: Vec<&MonitorGroup> = if opt.all {
+        groups.iter().collect()
+    } else {
+        opt.group
+            .iter()
+            .map(|name| groups.get(name))
+            .collect::<Result<_, _>>()?
+    };
 
-    for part in parts.into_iter().filter_map(|x| x) {
-        let (name, filter) = part.unwrap();
-        println!("Part: {}", name);
+    if !parts.is_empty() {
+        println!(
+            "Parts: {}",
+            parts
+                .iter()
+                .map(|part| part.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
 
         let _: Vec<_> = data_paths
             .par_iter()
@@ -109,30 +81,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 if !path.is_dir() {
                     create_dir(&path).expect(&format!("Failed to create dir: {:?}", path))
                 }
-                let mut filename = path.join(name).with_extension("png");
-                if name == "m1-segments" {
-                    match Mirror::m1(arg).net_force().load() {
-                        Ok(mut m1) => {
-                            if let Some(arg) = opt.last {
-                                m1.keep_last(arg);
-                            }
-                            m1.plot_forces(filename.to_str())
-                        }
-                        Err(e) => println!("{}: {:}", arg, e),
-                    }
-                } else {
-                    let mut monitors = Monitors::loader::<String, CFD_YEAR>(arg.clone())
-                        .header_filter(filter.to_string())
-                        //.exclude_filter(xmon)
-                        .load()
-                        .unwrap();
+                let case_monitors = Monitors::loader::<String, CFD_YEAR>(arg.clone())
+                    .load()
+                    .unwrap();
+                for part in &parts {
+                    let mut monitors = part.subset(&case_monitors).unwrap();
                     if let Some(arg) = opt.last {
                         monitors.keep_last(arg);
                     }
+                    let mut filename = path.join(&part.name).with_extension("png");
                     if opt.detrend {
                         monitors.detrend();
                         filename = path
-                            .join(format!("{}-detrend.png", name))
+                            .join(format!("{}-detrend.png", part.name))
                             .with_extension("png");
                     }
                     monitors.plot_forces(filename.to_str());
@@ -141,5 +102,29 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .collect();
     }
 
+    if opt.m1_segments {
+        println!("Part: m1-segments");
+
+        let _: Vec<_> = data_paths
+            .par_iter()
+            .map(|arg| {
+                let path = Path::new(arg).join("report");
+                if !path.is_dir() {
+                    create_dir(&path).expect(&format!("Failed to create dir: {:?}", path))
+                }
+                let filename = path.join("m1-segments").with_extension("png");
+                match Mirror::m1(arg).net_force().load() {
+                    Ok(mut m1) => {
+                        if let Some(arg) = opt.last {
+                            m1.keep_last(arg);
+                        }
+                        m1.plot_forces(filename.to_str())
+                    }
+                    Err(e) => println!("{}: {:}", arg, e),
+                }
+            })
+            .collect();
+    }
+
     Ok(())
 }
@@ -0,0 +1,49 @@
+//! CFD REPOSITORY HEALTH CHECK
+
+use parse_monitors::cfd;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "cfd_doctor",
+    about = "Checks the data files of the CFD cases before launching reports"
+)]
+struct Opt {
+    #[structopt(flatten)]
+    selection: cfd::CaseSelection,
+    /// CFD repository, overrides CFD_REPO
+    #[structopt(long)]
+    repo: Option<String>,
+    /// Reference CFD case for the monitors, e.g. zen30az000_OS7
+    #[structopt(long)]
+    reference: Option<String>,
+    /// Gap tolerance in sampling periods
+    #[structopt(long, default_value = "1.5")]
+    gap_tolerance: f64,
+    /// JSON output
+    #[structopt(long)]
+    json: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    let mut baseline = opt.selection.baseline::<2021>()?;
+    if let Some(repo) = &opt.repo {
+        baseline = baseline.with_root(repo);
+    }
+    let mut doctor = cfd::Doctor::default().gap_tolerance(opt.gap_tolerance);
+    if let Some(reference) = &opt.reference {
+        doctor = doctor.reference(reference.parse()?);
+    }
+    let report = doctor.examine(&baseline)?;
+    if opt.json {
+        println!("{}", report.to_json()?);
+    } else {
+        print!("{report}");
+    }
+    if !report.is_healthy() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
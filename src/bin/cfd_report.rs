@@ -19,6 +19,8 @@ struct Opt {
     windloads: bool,
     #[structopt(long)]
     htc: bool,
+    #[structopt(flatten)]
+    selection: cfd::CaseSelection,
 }
 
 //const CFD_YEAR: u32 = 2021;
@@ -30,8 +32,9 @@ fn main() -> anyhow::Result<()> {
     Arc::new(Some(vec![cfd::CfdCase::<2021>::colloquial(
         30, 45, "cd", 12,
     )?]));*/
+    let baseline = opt.selection.baseline::<2021>()?;
     let cases: Arc<Option<Vec<cfd::CfdCase<2021>>>> =
-        Arc::new(Some(cfd::Baseline::<2021>::default().into_iter().collect()));
+        Arc::new(Some(baseline.into_iter().collect()));
     let parts_base = 0;
 
     let mut tjh = vec![];
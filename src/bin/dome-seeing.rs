@@ -40,7 +40,7 @@ fn main() {
         //.extras()
         .into_iter()
         .collect::<Vec<cfd::CfdCase<2021>>>();
-    let root = cfd::Baseline::<2021>::path();
+    let root = cfd::Baseline::<2021>::default_path().unwrap();
     let wfe_labels = None;
     let pssn_labels = None;
     let truncate = Some((
@@ -78,6 +78,7 @@ fn main() {
                     if let Some(cfd_case_20) = cfd::Baseline::<OTHER_YEAR>::find(cfd_case_21) {
                         let ds_20 = DomeSeeing::load(
                             cfd::Baseline::<OTHER_YEAR>::default_path()
+                                .unwrap()
                                 .join(format!("{}", cfd_case_20)),
                         )
                         .unwrap();
@@ -9,7 +9,9 @@ fn main() {
     let results: Vec<_> = cfd_cases_21
         .into_par_iter()
         .map(|cfd_case_21| {
-            let path_to_case = cfd::Baseline::<2021>::path().join(format!("{}", cfd_case_21));
+            let path_to_case = cfd::Baseline::<2021>::default_path()
+                .unwrap()
+                .join(format!("{}", cfd_case_21));
             let ds_21 = DomeSeeing::load(path_to_case.clone()).unwrap();
             if let (Some(v_pssn), Some(h_pssn)) = (ds_21.pssn(Band::V), ds_21.pssn(Band::H)) {
                 let wfe_rms =
@@ -18,7 +20,9 @@ fn main() {
                     (cfd_case_21.clone(), wfe_rms, v_pssn, h_pssn),
                     if let Some(cfd_case_20) = cfd::Baseline::<2020>::find(cfd_case_21) {
                         let ds_20 = DomeSeeing::load(
-                            cfd::Baseline::<2020>::path().join(format!("{}", cfd_case_20)),
+                            cfd::Baseline::<2020>::default_path()
+                                .unwrap()
+                                .join(format!("{}", cfd_case_20)),
                         )
                         .unwrap();
                         if let (Some(v_pssn), Some(h_pssn)) =
@@ -1,4 +1,4 @@
-use parse_monitors::{cfd, cfd::BaselineTrait, Monitors, Vector};
+use parse_monitors::{cfd, cfd::BaselineTrait, MonitorGroups, Monitors, Vector};
 use rayon::prelude::*;
 use std::{
     fs::{create_dir, File},
@@ -30,16 +30,8 @@ fn main() -> anyhow::Result<()> {
         }
     };
 
-    let parts = vec![
-        "C-Rings",
-        "GIR",
-        "LGS",
-        "M1 assembly",
-        "M1 covers",
-        r#"M2 \& Top-End"#,
-        "Trusses",
-        r#"Platform \& Trays"#,
-    ];
+    let groups = MonitorGroups::mount();
+    let cfd_root = cfd::Baseline::<2021>::default_path()?;
 
     let (latex,graphics): (Vec<_>,Vec<_>) = cfd::Baseline::<2021>::default().into_iter().collect::<Vec<cfd::CfdCase<2021>>>().into_par_iter().map(|cfd_case| {
         println!("{cfd_case}");
@@ -54,81 +46,29 @@ fn main() -> anyhow::Result<()> {
             r#"\midrule\multicolumn{{13}}{{l}}{{{}}}\\\hline"#,
             cfd_case.to_pretty_string()
         ));
-        let data_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+        let data_path = cfd_root.join(cfd_case.to_string());
 	let report_path = data_path.join("report");
 	if !report_path.is_dir() {
             create_dir(&report_path).unwrap()
 	}
 
-        let mut total_forces: Option<Vec<Vector>> = None;
-        let mut total_moments: Option<Vec<Vector>> = None;
+        let mut monitors = Monitors::loader::<PathBuf, 2021>(data_path.clone())
+            .load().unwrap();
+        monitors.keep_last(400);
+        let mut grouped = monitors.groups(&groups).unwrap();
 
-        for i in 0..8 {
-            let mut groups = vec![
-                "Cring",
-                "GIR",
-                "LGS",
-                "M1",
-                "M1cov",
-                "M2|Topend",
-                "Tbot|Tup|arm|cabletruss",
-                "cabletrays|platform|Cabs",
-            ];
-            let group = groups.remove(i);
-            if i == 4 {
-                groups.remove(3);
-            }
-            groups.push("M1level");
-
-            /*let mon = if cfd_case == cfd::CfdCase::colloquial(30, 135, "nos", 7).unwrap() {
-                Monitors::loader::<PathBuf, 2021>(data_path.clone())
-                    .exclude_filter(groups.join("|"))
-                    //.header_filter("M1")
-                    .header_filter(group)
-                    //.exclude_filter("floor|enclosure|screen|shutter")
-                    .start_time(200.)
-                    .end_time(340.)
-                    .load().unwrap()
-            } else {*/
-                let mut mon = Monitors::loader::<PathBuf, 2021>(data_path.clone())
-                    .exclude_filter(groups.join("|"))
-                    //.header_filter("M1")
-                    .header_filter(group)
-                    //.exclude_filter("floor|enclosure|screen|shutter")
-                    .load().unwrap();
-                mon.keep_last(400);
-                /*mon
-            };*/
-            /*
-                        println!("{:?}", mon.forces_and_moments.keys());
-                        println!(
-                            "{:?}",
-                            mon.forces_and_moments
-                                .values()
-                                .filter_map(|x| x.get(0).unwrap().force.magnitude())
-                                .collect::<Vec<_>>()
-                        );
-            */
-            //mon.summary();
-            let n = mon.len();
-            let (part_total_forces, part_total_moments): (Vec<_>, Vec<_>) =
-                mon.forces_and_moments.values().fold(
-                    (vec![Vector::zero(); n], vec![Vector::zero(); n]),
-                    |(mut fa, mut ma), value| {
-                        fa.iter_mut()
-                            .zip(value.iter())
-                            .for_each(|(mut fa, e)| fa += &e.force);
-                        ma.iter_mut()
-                            .zip(value.iter())
-                            .for_each(|(mut ma, e)| ma += &e.moment);
-                        (fa, ma)
-                    },
-                );
+        for group in groups.names() {
+            let Some(exertion) = grouped.forces_and_moments.get(group) else {
+                continue;
+            };
+            let part_total_forces: Vec<_> = exertion.iter().map(|e| e.force.clone()).collect();
+            let part_total_moments: Vec<_> = exertion.iter().map(|e| e.moment.clone()).collect();
+            let part = group.replace('&', r"\&");
             match (stats(&part_total_forces), stats(&part_total_moments)) {
                 (Some((f_mean, f_std)), Some((m_mean, m_std))) => {
                     latex.push(format!(
                         r#"{:>20} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} & {:.0} \\"#,
-                        parts[i],
+                        part,
                         f_mean[0],
                         f_mean[1],
                         f_mean[2],
@@ -145,7 +85,12 @@ fn main() -> anyhow::Result<()> {
                 }
                 _ => (),
             };
-	    let filename = format!("{}_total_forces_psds.png",group.replace("|","-").to_lowercase());
+	    let filename = format!(
+                "{}_total_forces_psds.png",
+                group
+                    .to_lowercase()
+                    .replace(|c: char| !c.is_ascii_alphanumeric(), "-")
+            );
             let filepath = format!(
             "{:}",
             data_path
@@ -168,32 +113,27 @@ fn main() -> anyhow::Result<()> {
 \subsection{{{}}}
 \includegraphics[width=0.8\textwidth]{{{{{{{:?}}}}}}}
 "#,
-	    parts[i],
+	    part,
             data_path
                 .join("report")
                 .join(filename)
                 .with_extension(""),
 	));
-            if let Some(total_forces) = total_forces.as_mut() {
-                total_forces
-                    .iter_mut()
-                    .zip(part_total_forces.as_slice())
-                    .for_each(|(mut t, p)| t += p);
-            } else {
-                total_forces = Some(part_total_forces);
-            }
-            if let Some(total_moments) = total_moments.as_mut() {
-                total_moments
-                    .iter_mut()
-                    .zip(part_total_moments.as_slice())
-                    .for_each(|(mut t, p)| t += p);
-            } else {
-                total_moments = Some(part_total_moments);
-            }
         }
+        grouped.total_exertion();
+        let total_forces: Vec<_> = grouped
+            .total_forces_and_moments
+            .iter()
+            .map(|e| e.force.clone())
+            .collect();
+        let total_moments: Vec<_> = grouped
+            .total_forces_and_moments
+            .iter()
+            .map(|e| e.moment.clone())
+            .collect();
         match (
-            stats(total_forces.as_ref().unwrap()),
-            stats(total_moments.as_ref().unwrap()),
+            stats(&total_forces),
+            stats(&total_moments),
         ) {
             (Some((f_mean, f_std)), Some((m_mean, m_std))) => {
                 latex.push(format!(
@@ -230,7 +170,7 @@ fn main() -> anyhow::Result<()> {
 		xaxis(complot::Axis::new().label("Time [s]")).
 		yaxis(complot::Axis::new().label("FORCE [N]"))
 		.legend(vec!["Fx","Fy","Fz"]);
-        Monitors::plot_this_forces(total_forces.as_ref().unwrap(),
+        Monitors::plot_this_forces(&total_forces,
 				   Some(plot));
         let filename = format!(
             "{:}",
@@ -247,7 +187,7 @@ fn main() -> anyhow::Result<()> {
 		xaxis(complot::Axis::new().label("Frequency [Hz]")).
 		yaxis(complot::Axis::new().label("FORCE PSD [N^2/Hz]"))
 		.legend(vec!["Fx","Fy","Fz"]);
-        Monitors::plot_this_forces_psds(total_forces.as_ref().unwrap(),
+        Monitors::plot_this_forces_psds(&total_forces,
 					Some(plot));
         let filename = format!(
             "{:}",
@@ -264,7 +204,7 @@ fn main() -> anyhow::Result<()> {
 		xaxis(complot::Axis::new().label("Time [s]")).
 		yaxis(complot::Axis::new().label("MOMENT [N.m]"))
 		.legend(vec!["Mx","My","Mz"]);
-        Monitors::plot_this_forces(total_moments.as_ref().unwrap(),
+        Monitors::plot_this_forces(&total_moments,
 				   Some(plot));
         let filename = format!(
             "{:}",
@@ -281,7 +221,7 @@ fn main() -> anyhow::Result<()> {
 		xaxis(complot::Axis::new().label("Frequency [Hz]")).
 		yaxis(complot::Axis::new().label("MOMENT PSD [(N.m)^2/Hz]"))
 		.legend(vec!["Mx","My","Mz"]);
-        Monitors::plot_this_forces_psds(total_moments.as_ref().unwrap(),
+        Monitors::plot_this_forces_psds(&total_moments,
 					Some(plot));
 
         let graphics= format!(
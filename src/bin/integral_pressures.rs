@@ -2,11 +2,13 @@
 
 use glob::glob;
 use indicatif::ParallelProgressIterator;
-use parse_monitors::{cfd, pressure::Pressure};
+use parse_monitors::{cfd, cfd::BaselineTrait, pressure::Pressure};
 use rayon::prelude::*;
 use std::{error::Error, path::Path, time::Instant};
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let cfd_root = cfd::Baseline::<2021>::default_path()?;
+    let time_stamps = cfd::CfdDataFile::<2021>::M2Pressure.time_stamps();
     cfd::Baseline::<2021>::default()
         .extras()
         .into_iter()
@@ -24,7 +26,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         .into_iter()
         .for_each(|cfd_case| {
             let now = Instant::now();
-            let case_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+            let case_path = cfd_root.join(cfd_case.to_string());
             let files: Vec<_> = glob(
                 case_path
                     .join("pressures")
@@ -44,12 +46,9 @@ fn main() -> Result<(), Box<dyn Error>> {
                 //.progress_count(n_files as u64)
                 .map(|file| {
                     let path = Path::new(file);
-                    let stem = Path::new(path.file_stem().unwrap())
-                        .file_stem()
-                        .unwrap()
-                        .to_str()
-                        .unwrap();
-                    let time = &stem[8..].parse::<f64>().unwrap();
+                    let time = &time_stamps
+                        .parse(path)
+                        .expect("failed to parse the file time stamp");
                     let csv_pressure = Pressure::decompress(path.to_path_buf()).unwrap();
                     let csv_geometry =
                         Pressure::decompress(path.with_file_name("M2p.csv.bz2")).unwrap();
@@ -4,22 +4,29 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 #[structopt(name = "lscfd")]
 struct Opt {
-    /// Zenith angle
+    /// Zenith angle, ignored if a baseline manifest is given
     #[structopt(short, long, default_value = "30")]
     zenith: u32,
     /// Case file
     #[structopt(long)]
     file: Option<String>,
+    #[structopt(flatten)]
+    selection: cfd::CaseSelection,
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
 
     let file = opt.file.unwrap_or_default();
-    let cfd_cases: Vec<_> =
-        cfd::Baseline::<2021>::at_zenith(cfd::ZenithAngle::new(opt.zenith).unwrap())
-            .into_iter()
-            .map(|case| format!("{}/{}", case.to_string(), file))
-            .collect();
-    println!("{}", cfd_cases.join(" "))
+    let baseline =
+        opt.selection
+            .select(cfd::Baseline::<2021>::at_zenith(cfd::ZenithAngle::new(
+                opt.zenith,
+            )?))?;
+    let cfd_cases: Vec<_> = baseline
+        .into_iter()
+        .map(|case| format!("{}/{}", case.to_string(), file))
+        .collect();
+    println!("{}", cfd_cases.join(" "));
+    Ok(())
 }
@@ -1,4 +1,6 @@
-use parse_monitors::{Mirror, Monitors, MonitorsLoader};
+#[cfg(feature = "windloading")]
+use parse_monitors::windloading::{self, FemInput, WindLoading};
+use parse_monitors::{FemNodes, Mirror, Monitors, MonitorsLoader, Validation};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -6,6 +8,9 @@ use structopt::StructOpt;
 struct Opt {
     /// Path to the monitor file repository
     path: String,
+    /// List the monitors in the monitors file header and exit
+    #[structopt(long)]
+    list: bool,
     /// Monitors regular expression filter
     #[structopt(short, long)]
     monitor: Option<String>,
@@ -20,7 +25,7 @@ struct Opt {
     end: Option<f64>,
     /// Truncate monitors to the `last` seconds
     #[structopt(short, long)]
-    last: Option<usize>,
+    last: Option<f64>,
     /// Save monitors to CSV file
     #[structopt(long)]
     csv: Option<String>,
@@ -30,9 +35,14 @@ struct Opt {
     /// Evaluates the moments at the part location instead of the OSS
     #[structopt(long)]
     local: bool,
-    ///// Write M1 mirror covers loads to `windloads.pkl`
-    //#[structopt(long = "m1-covers")]
-    //m1_covers: bool,
+    /// FEM nodes CSV or JSON file used with `--local` instead of the M1 covers nodes
+    #[structopt(long)]
+    nodes: Option<String>,
+    /// Write the wind loads of the FEM inputs listed in the given TOML or JSON file
+    /// to `windloads.parquet` in the monitor file repository
+    #[cfg(feature = "windloading")]
+    #[structopt(long)]
+    windloading: Option<String>,
     /// Display M1 force table summary
     #[structopt(long)]
     m1_table: bool,
@@ -42,6 +52,9 @@ struct Opt {
     /// Remove linear trends from monitors
     #[structopt(long)]
     detrend: bool,
+    /// Fail on monitors with missing force or moment components
+    #[structopt(long)]
+    strict: bool,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -64,16 +77,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     if let Some(arg) = opt.end {
         loader = loader.end_time(arg);
     }
+    if opt.strict {
+        loader = loader.validation(Validation::Strict);
+    }
+    if opt.list {
+        print!("{}", loader.catalog()?);
+        return Ok(());
+    }
 
     let mut monitors = loader.load()?;
     if let Some(arg) = opt.last {
-        monitors.keep_last(arg);
+        monitors = monitors.last(arg);
     }
     if opt.detrend {
         monitors.detrend();
     }
     if opt.local {
-        monitors.into_local();
+        if let Some(path) = opt.nodes {
+            monitors.into_local_with(&FemNodes::from_path(path)?, FemNodes::NODE);
+        } else {
+            monitors.into_local();
+        }
     }
     monitors.summary();
 
@@ -97,8 +121,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         monitors.to_csv(filename)?;
     }
     #[cfg(feature = "windloading")]
-    if opt.m1_covers {
-        monitors.m1covers_windloads()?;
+    if let Some(inputs) = opt.windloading {
+        let (Some(&start), Some(&end)) = (monitors.time.first(), monitors.time.last()) else {
+            return Err(format!("no monitors to write to {}", windloading::WINDLOADS).into());
+        };
+        let m1 = Mirror::m1(&opt.path)
+            .start_time(start)
+            .end_time(end)
+            .load()?;
+        WindLoading::builder(&monitors)
+            .inputs(FemInput::from_path(inputs)?)
+            .mirror(&m1)
+            .build()?
+            .to_parquet(std::path::Path::new(&opt.path).join(windloading::WINDLOADS))?;
     }
 
     if opt.m1_table {
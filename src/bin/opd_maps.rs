@@ -10,6 +10,7 @@ use std::{error::Error, time::Instant};
 
 fn main() -> Result<(), Box<dyn Error>> {
     let pattern = "optvol_optvol*.npz";
+    let cfd_root = cfd::Baseline::<2021>::default_path()?;
 
     cfd::Baseline::<2021>::default()
         //.extras()
@@ -18,7 +19,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         .into_par_iter()
         .for_each(|cfd_case| {
             let now = Instant::now();
-            let case_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+            let case_path = cfd_root.join(cfd_case.to_string());
             let files: Vec<_> = glob(case_path.join("optvol").join(pattern).to_str().unwrap())
                 .unwrap()
                 .collect();
@@ -10,7 +10,7 @@
 
 use std::env;
 
-use parse_monitors::cfd;
+use parse_monitors::{cfd, cfd::BaselineTrait};
 use plotters::prelude::*;
 use polars::prelude::*;
 use rayon::prelude::*;
@@ -26,7 +26,7 @@ fn main() -> Result<()> {
         .map(|data_path| {
             let mut df = {
                 let filename = format!("{}_pressure-stats.csv", mirror);
-                let path = cfd::Baseline::<2021>::path()
+                let path = cfd::Baseline::<2021>::default_path()?
                     .join(data_path.to_string())
                     .join(filename);
                 CsvReader::from_path(path)?
@@ -37,7 +37,7 @@ fn main() -> Result<()> {
             df.sort_in_place("Time [s]", false)?;
 
             let filename = format!("{}_pressure-stats_{}.png", mirror, stats.to_lowercase());
-            let path = cfd::Baseline::<2021>::path()
+            let path = cfd::Baseline::<2021>::default_path()?
                 .join(data_path.to_string())
                 .join(filename);
 
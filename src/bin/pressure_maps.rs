@@ -5,6 +5,7 @@
 use parse_monitors::{cfd, cfd::BaselineTrait, pressure::Pressure};
 use rayon::prelude::*;
 use std::{path::Path, time::Instant};
+use structopt::StructOpt;
 
 trait Config {
     fn configure(cfd_case: cfd::CfdCase<2021>) -> anyhow::Result<(String, Vec<String>)>;
@@ -14,7 +15,7 @@ impl Config for geotrans::M1 {
         Ok((
             "M1p.csv.z".to_string(),
             cfd::CfdDataFile::<2021>::M1Pressure
-                .glob(cfd_case)?
+                .glob(&cfd::Baseline::default(), cfd_case)?
                 .into_iter()
                 .map(|p| p.to_str().unwrap().to_string())
                 .collect(),
@@ -26,7 +27,7 @@ impl Config for geotrans::M2 {
         Ok((
             "M2p.csv.z".to_string(),
             cfd::CfdDataFile::<2021>::M2Pressure
-                .glob(cfd_case)?
+                .glob(&cfd::Baseline::default(), cfd_case)?
                 .into_iter()
                 .map(|p| p.to_str().unwrap().to_string())
                 .collect(),
@@ -37,13 +38,15 @@ impl Config for geotrans::M2 {
 fn main() -> anyhow::Result<()> {
     type M12 = geotrans::M2;
 
-    cfd::Baseline::<2021>::default()
+    let cfd_root = cfd::Baseline::<2021>::default_path()?;
+    cfd::CaseSelection::from_args()
+        .baseline::<2021>()?
         .into_iter()
         .collect::<Vec<cfd::CfdCase<2021>>>()
         .into_par_iter()
         .for_each(|cfd_case| {
             let now = Instant::now();
-            let case_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+            let case_path = cfd_root.join(cfd_case.to_string());
             let (_geometry, files) = M12::configure(cfd_case).unwrap();
 
             let _ = files.last().map(|file| {
@@ -8,6 +8,7 @@ use glob::glob;
 use parse_monitors::{cfd, cfd::BaselineTrait, pressure::Pressure};
 use rayon::prelude::*;
 use std::{error::Error, path::Path, time::Instant};
+use structopt::StructOpt;
 
 trait Config {
     fn configure(cfd_case: cfd::CfdCase<2021>) -> anyhow::Result<(String, Vec<String>)>;
@@ -17,7 +18,7 @@ impl Config for geotrans::M1 {
         Ok((
             "M1p.csv.z".to_string(),
             cfd::CfdDataFile::<2021>::M1Pressure
-                .glob(cfd_case)?
+                .glob(&cfd::Baseline::default(), cfd_case)?
                 .into_iter()
                 .map(|p| p.to_str().unwrap().to_string())
                 .collect(),
@@ -29,7 +30,7 @@ impl Config for geotrans::M2 {
         Ok((
             "M2p.csv.z".to_string(),
             cfd::CfdDataFile::<2021>::M2Pressure
-                .glob(cfd_case)?
+                .glob(&cfd::Baseline::default(), cfd_case)?
                 .into_iter()
                 .map(|p| p.to_str().unwrap().to_string())
                 .collect(),
@@ -39,28 +40,26 @@ impl Config for geotrans::M2 {
 
 fn main() -> Result<(), Box<dyn Error>> {
     type M12 = geotrans::M1;
-    cfd::Baseline::<2021>::default()
-        //.extras()
-        .into_iter()
-        /*
-                   .filter(|c| {
-                       *c == cfd::CfdCase::new(
-                           cfd::ZenithAngle::Thirty,
-                           cfd::Azimuth::FortyFive,
-                           cfd::Enclosure::OpenStowed,
-                           cfd::WindSpeed::Seven,
-                       )
-                   })
-        */
-        //        .skip(1)
-        //.collect::<Vec<cfd::CfdCase<2021>>>()
-        //.into_par_iter()
-        .nth(7)
+    let cfd_root = cfd::Baseline::<2021>::default_path()?;
+    let time_stamps = cfd::CfdDataFile::<2021>::M1Pressure.time_stamps();
+    let selection = cfd::CaseSelection::from_args();
+    // without any selection, only the 8th case of the baseline is processed
+    let cfd_cases: Vec<_> = if selection.baseline.is_none() && selection.cases.is_none() {
+        cfd::Baseline::<2021>::default()
+            .into_iter()
+            .nth(7)
+            .into_iter()
+            .collect()
+    } else {
+        selection.baseline::<2021>()?.into_iter().collect()
+    };
+    cfd_cases
         .into_iter()
+        //.extras()
         .for_each(|cfd_case| {
             println!("{cfd_case}");
             let now = Instant::now();
-            let case_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+            let case_path = cfd_root.join(cfd_case.to_string());
             let (_geometry, files) = M12::configure(cfd_case).unwrap();
             //let n_files = files.len();
 
@@ -71,12 +70,9 @@ fn main() -> Result<(), Box<dyn Error>> {
                 //.progress_count(n_files as u64)
                 .map(|file| {
                     let path = Path::new(file);
-                    let stem = Path::new(path.file_stem().unwrap())
-                        .file_stem()
-                        .unwrap()
-                        .to_str()
-                        .unwrap();
-                    let time = &stem[8..].parse::<f64>().unwrap();
+                    let time = &time_stamps
+                        .parse(path)
+                        .expect("failed to parse the file time stamp");
                     let csv_pressure = Pressure::<M12>::decompress(path.to_path_buf()).unwrap();
                     //let csv_geometry =
                     //    Pressure::<M12>::decompress(path.with_file_name("M2p.csv.bz2")).unwrap();
@@ -7,7 +7,8 @@ use std::time::Instant;
 fn main() -> anyhow::Result<()> {
     let case = cfd::CfdCase::colloquial(30, 0, "os", 7)?;
     println!("{}", case);
-    let paths = cfd::CfdDataFile::<2021>::TelescopePressure.glob(case)?;
+    let paths =
+        cfd::CfdDataFile::<2021>::TelescopePressure.glob(&cfd::Baseline::default(), case)?;
     let data_file = paths.last().unwrap();
     println!("{:?}", data_file);
     let now = Instant::now();
@@ -0,0 +1,214 @@
+//! # CFD case data files catalog
+//!
+//! The [CaseCatalog] lists all the [CfdDataFile]s of a CFD case together with
+//! their time stamps parsed from the file names
+//! e.g. `M1p_M1p_7.000000e+02.csv.z` is the M1 pressure at 700s.
+//! The data files of each kind are sorted in increasing time order.
+//!
+//! ```no_run
+//! use parse_monitors::cfd::{Baseline, CaseCatalog, CfdCase, CfdDataFile};
+//! let cfd_case = CfdCase::<2021>::colloquial(30, 45, "os", 7).unwrap();
+//! let catalog = CaseCatalog::new(&Baseline::<2021>::default(), cfd_case).unwrap();
+//! let last_400s = catalog.last(CfdDataFile::M1Pressure, 400.);
+//! println!(
+//!     "M1 pressure: {} files sampled at {:?}Hz",
+//!     last_400s.len(),
+//!     catalog.sampling_frequency(CfdDataFile::M1Pressure)
+//! );
+//! ```
+
+use super::{Baseline, BaselineTrait, CfdCase, CfdDataFile, Is2021or2025, Result};
+use regex::Regex;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// A CFD data file and its time stamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataFile {
+    /// Time stamp [s]
+    pub time: f64,
+    /// Path to the file
+    pub path: PathBuf,
+}
+
+/// Time-indexed catalog of the data files of a CFD case
+#[derive(Debug)]
+pub struct CaseCatalog<const YEAR: u32> {
+    cfd_case: CfdCase<YEAR>,
+    files: BTreeMap<CfdDataFile<YEAR>, Vec<DataFile>>,
+}
+impl<const YEAR: u32> CfdDataFile<YEAR>
+where
+    CfdCase<YEAR>: Is2021or2025,
+    Baseline<YEAR>: BaselineTrait<YEAR>,
+{
+    /// All the data file kinds
+    pub fn all() -> [Self; 5] {
+        use CfdDataFile::*;
+        [
+            M1Pressure,
+            M2Pressure,
+            TemperatureField,
+            OpticalPathDifference,
+            TelescopePressure,
+        ]
+    }
+    /// Returns the time stamp of a data file from its name
+    ///
+    /// The time stamp parser is compiled at each call, use [CfdDataFile::time_stamps] to parse many file names
+    pub fn time_stamp<P: AsRef<Path>>(self, path: P) -> Option<f64> {
+        self.time_stamps().parse(path)
+    }
+    /// Returns the time stamp parser of the data files
+    pub fn time_stamps(self) -> TimeStamps {
+        TimeStamps::new(&self.pattern())
+    }
+}
+/// Parser of the time stamp that follows the pattern of a [CfdDataFile] in the file names
+#[derive(Debug, Clone)]
+pub struct TimeStamps(Regex);
+impl TimeStamps {
+    fn new(pattern: &str) -> Self {
+        Self(
+            Regex::new(&format!(
+                r"^{}([-+]?\d+(?:\.\d*)?(?:[eE][-+]?\d+)?)",
+                regex::escape(pattern)
+            ))
+            .expect("invalid time stamp regular expression"),
+        )
+    }
+    /// Returns the time stamp of a data file from its name
+    pub fn parse<P: AsRef<Path>>(&self, path: P) -> Option<f64> {
+        let file_name = path.as_ref().file_name()?.to_str()?;
+        self.0.captures(file_name)?.get(1)?.as_str().parse().ok()
+    }
+}
+impl<const YEAR: u32> CaseCatalog<YEAR>
+where
+    CfdCase<YEAR>: Is2021or2025,
+    Baseline<YEAR>: BaselineTrait<YEAR>,
+{
+    /// Creates the catalog of the data files of a CFD case in the given baseline repository
+    pub fn new(baseline: &Baseline<YEAR>, cfd_case: CfdCase<YEAR>) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        for data_file in CfdDataFile::<YEAR>::all() {
+            let time_stamps = data_file.time_stamps();
+            let mut time_stamped: Vec<DataFile> = data_file
+                .glob(baseline, cfd_case)?
+                .into_iter()
+                .filter_map(|path| time_stamps.parse(&path).map(|time| DataFile { time, path }))
+                .collect();
+            time_stamped.sort_by(|a, b| a.time.total_cmp(&b.time));
+            files.insert(data_file, time_stamped);
+        }
+        Ok(Self { cfd_case, files })
+    }
+}
+impl<const YEAR: u32> CaseCatalog<YEAR> {
+    /// Returns the CFD case
+    pub fn cfd_case(&self) -> CfdCase<YEAR> {
+        self.cfd_case
+    }
+    /// Returns all the data files of a given kind in increasing time order
+    pub fn files(&self, data_file: CfdDataFile<YEAR>) -> &[DataFile] {
+        self.files
+            .get(&data_file)
+            .map(|files| files.as_slice())
+            .unwrap_or_default()
+    }
+    /// Returns the number of data files of a given kind
+    pub fn len(&self, data_file: CfdDataFile<YEAR>) -> usize {
+        self.files(data_file).len()
+    }
+    /// Returns true if there is no data file of a given kind
+    pub fn is_empty(&self, data_file: CfdDataFile<YEAR>) -> bool {
+        self.files(data_file).is_empty()
+    }
+    /// Returns the time stamps of the data files of a given kind
+    pub fn time(&self, data_file: CfdDataFile<YEAR>) -> Vec<f64> {
+        self.files(data_file).iter().map(|file| file.time).collect()
+    }
+    /// Returns the time stamps of the first and last data files of a given kind
+    pub fn time_range(&self, data_file: CfdDataFile<YEAR>) -> Option<(f64, f64)> {
+        let files = self.files(data_file);
+        files
+            .first()
+            .zip(files.last())
+            .map(|(a, b)| (a.time, b.time))
+    }
+    /// Returns the data files of a given kind with time stamps within `[start,end]`
+    pub fn time_window(&self, data_file: CfdDataFile<YEAR>, start: f64, end: f64) -> &[DataFile] {
+        let files = self.files(data_file);
+        let i = files.partition_point(|file| file.time < start);
+        let j = files.partition_point(|file| file.time <= end);
+        &files[i..j.max(i)]
+    }
+    /// Returns the data files of a given kind within the last `duration` seconds
+    pub fn last(&self, data_file: CfdDataFile<YEAR>, duration: f64) -> &[DataFile] {
+        match self.time_range(data_file) {
+            Some((_, end)) => self.time_window(data_file, end - duration, end),
+            None => &[],
+        }
+    }
+    /// Returns the sampling frequency [Hz] of the data files of a given kind
+    ///
+    /// The sampling frequency is the inverse of the median of the time stamps differences
+    pub fn sampling_frequency(&self, data_file: CfdDataFile<YEAR>) -> Option<f64> {
+        crate::median_time_step(&self.time(data_file)).map(f64::recip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_file_time_stamp() {
+        assert_eq!(
+            CfdDataFile::<2021>::M1Pressure.time_stamp("/data/M1p_M1p_7.000000e+02.csv.z"),
+            Some(700.)
+        );
+        assert_eq!(
+            CfdDataFile::<2021>::OpticalPathDifference.time_stamp("optvol_optvol_1.2525e+03.npz"),
+            Some(1252.5)
+        );
+        assert_eq!(
+            CfdDataFile::<2021>::M2Pressure.time_stamp("M2p.csv.z"),
+            None
+        );
+    }
+    #[test]
+    fn catalog_selection() {
+        let cfd_case = CfdCase::<2021>::colloquial(30, 45, "os", 7).unwrap();
+        let files: Vec<DataFile> = [3., 0.5, 1., 2., 1.5, 2.5]
+            .into_iter()
+            .map(|time| DataFile {
+                time,
+                path: PathBuf::from(format!("M1p_M1p_{time:e}.csv.z")),
+            })
+            .collect();
+        let mut catalog = CaseCatalog {
+            cfd_case,
+            files: BTreeMap::new(),
+        };
+        let mut sorted = files;
+        sorted.sort_by(|a, b| a.time.total_cmp(&b.time));
+        catalog.files.insert(CfdDataFile::M1Pressure, sorted);
+        let m1 = CfdDataFile::<2021>::M1Pressure;
+        assert_eq!(catalog.time(m1), vec![0.5, 1., 1.5, 2., 2.5, 3.]);
+        assert_eq!(catalog.time_range(m1), Some((0.5, 3.)));
+        assert_eq!(catalog.sampling_frequency(m1), Some(2.));
+        let window: Vec<f64> = catalog
+            .time_window(m1, 1., 2.)
+            .iter()
+            .map(|f| f.time)
+            .collect();
+        assert_eq!(window, vec![1., 1.5, 2.]);
+        let last: Vec<f64> = catalog.last(m1, 1.).iter().map(|f| f.time).collect();
+        assert_eq!(last, vec![2., 2.5, 3.]);
+        assert!(catalog.is_empty(CfdDataFile::M2Pressure));
+        assert!(catalog.time_window(m1, 5., 4.).is_empty());
+    }
+}
@@ -0,0 +1,402 @@
+//! # CFD repository health check
+//!
+//! The [Doctor] walks the cases of a [Baseline] and reports, for each case,
+//! the data files that are missing, the time coverage of the monitors and of the
+//! snapshots series, the gaps in the snapshots series and the monitors
+//! that are missing with respect to a reference case.
+//!
+//! ```no_run
+//! use parse_monitors::cfd::{Baseline, CfdCase, Doctor};
+//! let report = Doctor::default()
+//!     .reference(CfdCase::<2021>::colloquial(30, 0, "os", 7).unwrap())
+//!     .examine(&Baseline::<2021>::default())
+//!     .unwrap();
+//! println!("{report}");
+//! ```
+
+use super::{Baseline, CaseCatalog, CfdCase, CfdDataFile, Result};
+use crate::decompress;
+use serde::Serialize;
+use std::{
+    fmt,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+/// Health of a series of snapshots
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesHealth {
+    /// Data file kind
+    pub kind: String,
+    /// Number of snapshots
+    pub count: usize,
+    /// Time stamps of the first and last snapshots [s]
+    pub time_range: Option<(f64, f64)>,
+    /// Sampling frequency [Hz]
+    pub sampling_frequency: Option<f64>,
+    /// Time stamps bracketing the gaps in the series [s]
+    pub gaps: Vec<(f64, f64)>,
+}
+
+/// Health of a CFD case
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseHealth {
+    /// CFD case name
+    pub cfd_case: String,
+    /// Path to the CFD case
+    pub path: PathBuf,
+    /// A `monitors` file with one of the [decompress::CSV_EXTENSIONS] extensions is present
+    pub monitors: bool,
+    /// Time stamps of the first and last monitors samples [s]
+    pub monitors_time_range: Option<(f64, f64)>,
+    /// Monitors of the reference case that are missing
+    pub missing_monitors: Vec<String>,
+    /// `center_of_pressure.csv` is present
+    pub center_of_pressure: bool,
+    /// `M2_segments_force.csv` is present
+    pub m2_segments_force: bool,
+    /// `domeseeing_PSSN.rs.pkl` is present
+    pub domeseeing: bool,
+    /// Number of images in `scenes`
+    pub scenes: usize,
+    /// Snapshots series
+    pub snapshots: Vec<SeriesHealth>,
+    /// Errors met while examining the case
+    pub errors: Vec<String>,
+}
+impl CaseHealth {
+    /// Returns true if all the data files are present, without gaps, missing monitors or errors
+    pub fn is_healthy(&self) -> bool {
+        self.monitors
+            && self.center_of_pressure
+            && self.m2_segments_force
+            && self.domeseeing
+            && self.scenes > 0
+            && self.missing_monitors.is_empty()
+            && self.errors.is_empty()
+            && self
+                .snapshots
+                .iter()
+                .all(|series| series.count > 0 && series.gaps.is_empty())
+    }
+    fn gaps(&self) -> usize {
+        self.snapshots.iter().map(|series| series.gaps.len()).sum()
+    }
+}
+
+/// Health report of a baseline
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport(Vec<CaseHealth>);
+impl Deref for HealthReport {
+    type Target = Vec<CaseHealth>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl HealthReport {
+    /// Returns true if all the cases are healthy
+    pub fn is_healthy(&self) -> bool {
+        self.0.iter().all(CaseHealth::is_healthy)
+    }
+    /// Returns the report as a JSON string
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+fn check(present: bool) -> &'static str {
+    if present {
+        "x"
+    } else {
+        "-"
+    }
+}
+impl fmt::Display for HealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds: Vec<String> = self
+            .0
+            .first()
+            .map(|case| case.snapshots.iter().map(|s| s.kind.clone()).collect())
+            .unwrap_or_default();
+        write!(
+            f,
+            "{:<16} {:>4} {:>4} {:>4} {:>5} {:>6}",
+            "CFD case", "MON", "CoP", "M2F", "PSSn", "scenes"
+        )?;
+        for kind in &kinds {
+            write!(f, " {:>7}", kind)?;
+        }
+        writeln!(
+            f,
+            " {:>17} {:>5} {:>7}",
+            "monitors time [s]", "gaps", "missing"
+        )?;
+        for case in &self.0 {
+            write!(
+                f,
+                "{:<16} {:>4} {:>4} {:>4} {:>5} {:>6}",
+                case.cfd_case,
+                check(case.monitors),
+                check(case.center_of_pressure),
+                check(case.m2_segments_force),
+                check(case.domeseeing),
+                case.scenes
+            )?;
+            for series in &case.snapshots {
+                write!(f, " {:>7}", series.count)?;
+            }
+            let time_range = case
+                .monitors_time_range
+                .map(|(start, end)| format!("{start:.1}-{end:.1}"))
+                .unwrap_or_else(|| "-".to_string());
+            writeln!(
+                f,
+                " {:>17} {:>5} {:>7}",
+                time_range,
+                case.gaps(),
+                case.missing_monitors.len()
+            )?;
+        }
+        for case in self.0.iter().filter(|case| !case.is_healthy()) {
+            for series in case.snapshots.iter().filter(|s| !s.gaps.is_empty()) {
+                let gaps: Vec<String> = series
+                    .gaps
+                    .iter()
+                    .map(|(start, end)| format!("{start}-{end}"))
+                    .collect();
+                writeln!(
+                    f,
+                    "{}: {} gaps at {}s",
+                    case.cfd_case,
+                    series.kind,
+                    gaps.join(", ")
+                )?;
+            }
+            if !case.missing_monitors.is_empty() {
+                writeln!(
+                    f,
+                    "{}: missing monitors {}",
+                    case.cfd_case,
+                    case.missing_monitors.join(", ")
+                )?;
+            }
+            for error in &case.errors {
+                writeln!(f, "{}: {}", case.cfd_case, error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// CFD repository health check
+#[derive(Debug, Clone)]
+pub struct Doctor {
+    reference: Option<CfdCase<2021>>,
+    gap_tolerance: f64,
+}
+impl Default for Doctor {
+    fn default() -> Self {
+        Self {
+            reference: None,
+            gap_tolerance: 1.5,
+        }
+    }
+}
+impl Doctor {
+    /// Sets the CFD case the monitors of the other cases are compared to
+    pub fn reference(self, cfd_case: CfdCase<2021>) -> Self {
+        Self {
+            reference: Some(cfd_case),
+            ..self
+        }
+    }
+    /// Sets the gap tolerance
+    ///
+    /// A gap is a time interval between 2 snapshots larger than `tolerance` times the sampling period (default: 1.5)
+    pub fn gap_tolerance(self, tolerance: f64) -> Self {
+        Self {
+            gap_tolerance: tolerance,
+            ..self
+        }
+    }
+    /// Examines all the cases of a baseline
+    pub fn examine(&self, baseline: &Baseline<2021>) -> Result<HealthReport> {
+        let reference_monitors = match self.reference {
+            Some(cfd_case) => {
+                monitors_summary(&baseline.case_path(cfd_case)?).and_then(|m| m.ok().map(|m| m.0))
+            }
+            None => None,
+        };
+        let report = baseline
+            .clone()
+            .into_iter()
+            .map(|cfd_case| self.examine_case(baseline, cfd_case, reference_monitors.as_deref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(HealthReport(report))
+    }
+    fn examine_case(
+        &self,
+        baseline: &Baseline<2021>,
+        cfd_case: CfdCase<2021>,
+        reference_monitors: Option<&[String]>,
+    ) -> Result<CaseHealth> {
+        let path = baseline.case_path(cfd_case)?;
+        let mut errors = vec![];
+        if !path.is_dir() {
+            errors.push(format!("{path:?} is not a directory"));
+        }
+
+        let (monitors, monitors_time_range, missing_monitors) = match monitors_summary(&path) {
+            Some(Ok((names, time_range))) => {
+                let missing = reference_monitors
+                    .map(|reference| {
+                        reference
+                            .iter()
+                            .filter(|name| !names.contains(name))
+                            .cloned()
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (true, time_range, missing)
+            }
+            Some(Err(e)) => {
+                errors.push(e);
+                (true, None, vec![])
+            }
+            None => (false, None, vec![]),
+        };
+
+        let catalog = CaseCatalog::new(baseline, cfd_case)?;
+        let snapshots = CfdDataFile::<2021>::all()
+            .into_iter()
+            .map(|kind| {
+                let time = catalog.time(kind);
+                let sampling_frequency = catalog.sampling_frequency(kind);
+                let gaps = sampling_frequency
+                    .map(|fs| {
+                        time.windows(2)
+                            .filter(|t| (t[1] - t[0]) * fs > self.gap_tolerance)
+                            .map(|t| (t[0], t[1]))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                SeriesHealth {
+                    kind: kind.short_name().to_string(),
+                    count: time.len(),
+                    time_range: catalog.time_range(kind),
+                    sampling_frequency,
+                    gaps,
+                }
+            })
+            .collect();
+
+        let scenes = glob::glob(path.join("scenes").join("*.png").to_str().unwrap())?
+            .filter_map(|p| p.ok())
+            .count();
+
+        Ok(CaseHealth {
+            cfd_case: cfd_case.to_string(),
+            monitors,
+            monitors_time_range,
+            missing_monitors,
+            center_of_pressure: path.join("center_of_pressure.csv").is_file(),
+            m2_segments_force: path.join("M2_segments_force.csv").is_file(),
+            domeseeing: path.join("domeseeing_PSSN.rs.pkl").is_file(),
+            scenes,
+            snapshots,
+            errors,
+            path,
+        })
+    }
+}
+impl<const YEAR: u32> CfdDataFile<YEAR> {
+    /// Short name used in reports
+    pub fn short_name(self) -> &'static str {
+        use CfdDataFile::*;
+        match self {
+            M1Pressure => "M1p",
+            M2Pressure => "M2p",
+            TemperatureField => "T",
+            OpticalPathDifference => "OPD",
+            TelescopePressure => "Telp",
+        }
+    }
+}
+
+type MonitorsSummary = (Vec<String>, Option<(f64, f64)>);
+/// Reads the monitors names and time range from the monitors file in `path`
+///
+/// Returns `None` if there is no monitors file
+fn monitors_summary(path: &Path) -> Option<std::result::Result<MonitorsSummary, String>> {
+    let monitors = decompress::find(path.join("monitors.csv"), decompress::CSV_EXTENSIONS).ok()?;
+    let reader = match decompress::open(&monitors) {
+        Ok(reader) => reader,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+    let mut rdr = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let summary = (|| {
+        let names: Vec<String> = rdr
+            .headers()?
+            .iter()
+            .skip(1)
+            .map(|h| h.trim().to_string())
+            .collect();
+        let mut record = csv::StringRecord::new();
+        let mut time_range: Option<(f64, f64)> = None;
+        while rdr.read_record(&mut record)? {
+            if let Some(Ok(time)) = record.get(0).map(|t| t.trim().parse::<f64>()) {
+                time_range = Some(time_range.map_or((time, time), |(start, _)| (start, time)));
+            }
+        }
+        Ok::<_, csv::Error>((names, time_range))
+    })();
+    Some(summary.map_err(|e| format!("{:?}: {e}", path.join("monitors"))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::{fs, io::Write};
+
+    #[test]
+    fn doctor_examine() {
+        let root = std::env::temp_dir().join(format!("doctor-{}", std::process::id()));
+        let healthy = CfdCase::<2021>::colloquial(30, 0, "os", 7).unwrap();
+        let broken = CfdCase::<2021>::colloquial(30, 45, "os", 7).unwrap();
+        for (cfd_case, header) in [(healthy, "Time,Cabs_Fx,Cabs_Fy"), (broken, "Time,Cabs_Fx")] {
+            let path = root.join(cfd_case.to_string());
+            fs::create_dir_all(path.join("pressures")).unwrap();
+            let mut gz = GzEncoder::new(
+                std::fs::File::create(path.join("monitors.csv.z")).unwrap(),
+                Compression::fast(),
+            );
+            writeln!(gz, "{header}").unwrap();
+            for t in [0.5, 1.0] {
+                writeln!(gz, "{t},1,2").unwrap();
+            }
+            gz.finish().unwrap();
+        }
+        for t in [1., 1.5, 2., 3.5, 4.] {
+            fs::write(
+                root.join(broken.to_string())
+                    .join("pressures")
+                    .join(format!("M1p_M1p_{t:e}.csv.z")),
+                "",
+            )
+            .unwrap();
+        }
+        let baseline = Baseline::from(vec![healthy, broken]).with_root(&root);
+        let report = Doctor::default()
+            .reference(healthy)
+            .examine(&baseline)
+            .unwrap();
+        assert_eq!(report[0].monitors_time_range, Some((0.5, 1.0)));
+        assert!(report[0].missing_monitors.is_empty());
+        assert_eq!(report[1].missing_monitors, vec!["Cabs_Fy".to_string()]);
+        assert_eq!(report[1].snapshots[0].count, 5);
+        assert_eq!(report[1].snapshots[0].gaps, vec![(2., 3.5)]);
+        assert!(!report.is_healthy());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
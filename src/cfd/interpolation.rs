@@ -0,0 +1,305 @@
+//! # CFD baseline interpolation
+//!
+//! [Baseline::bracket] returns the CFD cases that bracket a telescope pointing and a wind speed
+//! together with their (tri)linear interpolation weights.
+//! The enclosure configuration of each case is the one given by [BaselineTrait::configuration]
+//! for the wind speeds of the bracketing cases.
+//!
+//! The baselines only cover the \[0,180\] degree azimuth range, an azimuth angle `a` above 180 degrees
+//! is bracketed with the cases at `360-a`, the mirror image of the pointing with respect to the
+//! telescope plane of symmetry (the OSS y-z plane), and the bracket is flagged as mirrored (see [Bracket::is_mirrored]).
+//! The statistics of a mirrored bracket are combined according to their [Parity].
+//!
+//! ```no_run
+//! use parse_monitors::cfd::{Baseline, Parity};
+//! let bracket = Baseline::<2021>::default().bracket(40., 60., 9.).unwrap();
+//! for (cfd_case, weight) in bracket.iter() {
+//!     println!("{cfd_case}: {weight:.3}");
+//! }
+//! // combining the mean of a force computed for each case
+//! let mean_force = bracket
+//!     .combine(Parity::Even, |cfd_case| {
+//!         Ok::<f64, std::io::Error>(f64::from(cfd_case.wind_speed))
+//!     })
+//!     .unwrap();
+//! ```
+
+use super::{Baseline, BaselineTrait, CfdCase, CfdError, Result, ZenithAngle};
+use std::{collections::BTreeMap, ops::Deref};
+
+/// Symmetry of a statistic with respect to the telescope plane of symmetry (the OSS y-z plane)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// The statistic is unchanged by the mirror symmetry, e.g. Fy, Fz, Mx or a pressure
+    Even,
+    /// The sign of the statistic is flipped by the mirror symmetry, e.g. Fx, My or Mz
+    Odd,
+}
+impl Parity {
+    /// Parities of the `[x,y,z]` components of a force
+    pub const FORCE: [Parity; 3] = [Parity::Odd, Parity::Even, Parity::Even];
+    /// Parities of the `[x,y,z]` components of a moment
+    pub const MOMENT: [Parity; 3] = [Parity::Even, Parity::Odd, Parity::Odd];
+}
+
+/// CFD cases and their interpolation weights
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bracket<const YEAR: u32> {
+    cases: Vec<(CfdCase<YEAR>, f64)>,
+    mirrored: bool,
+    clamped: bool,
+}
+impl<const YEAR: u32> Deref for Bracket<YEAR> {
+    type Target = Vec<(CfdCase<YEAR>, f64)>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cases
+    }
+}
+impl<const YEAR: u32> IntoIterator for Bracket<YEAR> {
+    type Item = (CfdCase<YEAR>, f64);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cases.into_iter()
+    }
+}
+impl<const YEAR: u32> Bracket<YEAR> {
+    /// Returns the CFD cases
+    pub fn cases(&self) -> Vec<CfdCase<YEAR>> {
+        self.cases.iter().map(|(cfd_case, _)| *cfd_case).collect()
+    }
+    /// Returns the CFD case with the largest weight
+    pub fn nearest(&self) -> CfdCase<YEAR> {
+        self.cases
+            .iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(cfd_case, _)| *cfd_case)
+            .expect("the bracket is never empty")
+    }
+    /// Returns true if the CFD cases are the mirror image of the requested pointing
+    ///
+    /// This is the case for azimuth angles within the (180,360) degree range
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+    /// Returns true if the zenith angle, the azimuth angle or the wind speed is outside the range of the baseline
+    ///
+    /// The out-of-range values are clamped to the nearest CFD cases
+    pub fn is_clamped(&self) -> bool {
+        self.clamped
+    }
+    /// Returns the sign of the statistics with the given parity
+    fn sign(&self, parity: Parity) -> f64 {
+        if self.mirrored && parity == Parity::Odd {
+            -1f64
+        } else {
+            1f64
+        }
+    }
+    /// Combines a scalar statistic computed for each CFD case with the interpolation weights
+    ///
+    /// The sign of an [Parity::Odd] statistic is flipped if the bracket is mirrored
+    pub fn combine<F, E>(&self, parity: Parity, mut stat: F) -> std::result::Result<f64, E>
+    where
+        F: FnMut(CfdCase<YEAR>) -> std::result::Result<f64, E>,
+    {
+        let sign = self.sign(parity);
+        self.cases
+            .iter()
+            .map(|(cfd_case, weight)| stat(*cfd_case).map(|value| sign * value * weight))
+            .sum()
+    }
+    /// Combines the scalar statistics of the CFD cases with the interpolation weights
+    ///
+    /// The sign of an [Parity::Odd] statistic is flipped if the bracket is mirrored.
+    /// Returns `None` if the statistic of one of the CFD cases is missing
+    pub fn combine_from(
+        &self,
+        parity: Parity,
+        stats: &BTreeMap<CfdCase<YEAR>, f64>,
+    ) -> Option<f64> {
+        let sign = self.sign(parity);
+        self.cases
+            .iter()
+            .map(|(cfd_case, weight)| stats.get(cfd_case).map(|value| sign * value * weight))
+            .sum()
+    }
+}
+
+/// Returns the values bracketing `x` and their linear interpolation weights
+/// and whether `x` has been clamped to the range of `values`
+///
+/// `values` must be sorted and not empty and `x` must be finite
+fn bracket_1d(values: &[f64], x: f64) -> Result<(Vec<(f64, f64)>, bool)> {
+    if !x.is_finite() {
+        return Err(CfdError::Bracket(format!("non-finite value {x}")));
+    }
+    let i = values.partition_point(|value| *value < x);
+    Ok(if i == values.len() {
+        (vec![(values[i - 1], 1f64)], true)
+    } else if values[i] == x {
+        (vec![(x, 1f64)], false)
+    } else if i == 0 {
+        (vec![(values[0], 1f64)], true)
+    } else {
+        let (a, b) = (values[i - 1], values[i]);
+        let w = (x - a) / (b - a);
+        (vec![(a, 1f64 - w), (b, w)], false)
+    })
+}
+fn sorted_unique(mut values: Vec<f64>) -> Vec<f64> {
+    values.sort_by(f64::total_cmp);
+    values.dedup();
+    values
+}
+
+impl<const YEAR: u32> Baseline<YEAR>
+where
+    Baseline<YEAR>: BaselineTrait<YEAR>,
+{
+    /// Returns the CFD cases of the baseline bracketing the given zenith angle [deg], azimuth angle [deg]
+    /// and wind speed [m/s] with their interpolation weights
+    ///
+    /// An azimuth angle within the (180,360) degree range is mirrored into the \[0,180\] degree range
+    /// of the baseline and the bracket is flagged as mirrored, see [Bracket::is_mirrored] and [Parity].
+    /// Values outside the range of the baseline are clamped to the nearest CFD cases
+    /// and the bracket is flagged as clamped, see [Bracket::is_clamped].
+    /// If several enclosures are available for the same wind speed, the one given by
+    /// [BaselineTrait::configuration] is selected.
+    pub fn bracket(&self, zenith: f64, azimuth: f64, wind_speed: f64) -> Result<Bracket<YEAR>> {
+        if !zenith.is_finite() {
+            return Err(CfdError::CustomZenithAngle(zenith));
+        }
+        if !azimuth.is_finite() {
+            return Err(CfdError::CustomAzimuth(azimuth));
+        }
+        if !wind_speed.is_finite() {
+            return Err(CfdError::CustomWindSpeed(wind_speed));
+        }
+        let cfd_cases: Vec<CfdCase<YEAR>> = self.clone().into_iter().collect();
+        let azimuth = azimuth.rem_euclid(360f64);
+        let mirrored = azimuth > 180f64;
+        let azimuth = if mirrored { 360f64 - azimuth } else { azimuth };
+        let no_case = |what: String| CfdError::Bracket(what);
+
+        let zeniths = sorted_unique(cfd_cases.iter().map(|c| f64::from(c.zenith)).collect());
+        if zeniths.is_empty() {
+            return Err(no_case("empty baseline".into()));
+        }
+        let mut weights: BTreeMap<CfdCase<YEAR>, f64> = BTreeMap::new();
+        let (zenith_bracket, mut clamped) = bracket_1d(&zeniths, zenith)?;
+        for (z, wz) in zenith_bracket {
+            let zenith_angle = ZenithAngle::custom(z)?;
+            let configuration = Self::configuration(zenith_angle);
+            let at_zenith: Vec<&CfdCase<YEAR>> = cfd_cases
+                .iter()
+                .filter(|c| c.zenith == zenith_angle)
+                .collect();
+            let wind_speeds =
+                sorted_unique(at_zenith.iter().map(|c| f64::from(c.wind_speed)).collect());
+            let (wind_bracket, wind_clamped) = bracket_1d(&wind_speeds, wind_speed)?;
+            clamped |= wind_clamped;
+            for (u, wu) in wind_bracket {
+                let with_wind: Vec<&CfdCase<YEAR>> = at_zenith
+                    .iter()
+                    .filter(|c| f64::from(c.wind_speed) == u)
+                    .cloned()
+                    .collect();
+                let enclosure = configuration
+                    .iter()
+                    .find(|(speed, enclosure)| {
+                        f64::from(*speed) == u
+                            && with_wind.iter().any(|c| c.enclosure == *enclosure)
+                    })
+                    .map(|(_, enclosure)| *enclosure)
+                    .unwrap_or(with_wind[0].enclosure);
+                let azimuths: Vec<f64> = sorted_unique(
+                    with_wind
+                        .iter()
+                        .filter(|c| c.enclosure == enclosure)
+                        .map(|c| f64::from(c.azimuth))
+                        .collect(),
+                );
+                let (azimuth_bracket, azimuth_clamped) = bracket_1d(&azimuths, azimuth)?;
+                clamped |= azimuth_clamped;
+                for (a, wa) in azimuth_bracket {
+                    let cfd_case = with_wind
+                        .iter()
+                        .find(|c| c.enclosure == enclosure && f64::from(c.azimuth) == a)
+                        .ok_or_else(|| no_case(format!("zenith {z}, azimuth {a}, wind {u}")))?;
+                    *weights.entry(**cfd_case).or_default() += wz * wu * wa;
+                }
+            }
+        }
+        Ok(Bracket {
+            cases: weights.into_iter().filter(|(_, w)| *w > 0f64).collect(),
+            mirrored,
+            clamped,
+        })
+    }
+    /// Returns the CFD case of the baseline nearest to the given zenith angle [deg], azimuth angle [deg]
+    /// and wind speed [m/s]
+    ///
+    /// The nearest CFD case is the bracketing case with the largest interpolation weight (see [Baseline::bracket])
+    pub fn nearest(&self, zenith: f64, azimuth: f64, wind_speed: f64) -> Result<CfdCase<YEAR>> {
+        Ok(self.bracket(zenith, azimuth, wind_speed)?.nearest())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bracket() {
+        let bracket = Baseline::<2021>::default().bracket(40., 60., 9.).unwrap();
+        assert_eq!(bracket.len(), 8);
+        assert!((bracket.iter().map(|(_, w)| w).sum::<f64>() - 1.).abs() < 1e-12);
+        let weight = |name: &str| {
+            let cfd_case: CfdCase<2021> = name.parse().unwrap();
+            bracket.iter().find(|(c, _)| *c == cfd_case).unwrap().1
+        };
+        assert!((weight("zen30az045_OS7") - 2. / 3. * 0.6 * 2. / 3.).abs() < 1e-12);
+        assert!((weight("zen60az090_CS12") - 1. / 3. * 0.4 * 1. / 3.).abs() < 1e-12);
+        assert_eq!(
+            bracket.nearest(),
+            "zen30az045_OS7".parse::<CfdCase<2021>>().unwrap()
+        );
+
+        assert!(!bracket.is_mirrored());
+        assert!(!bracket.is_clamped());
+
+        let on_grid = Baseline::<2021>::default().bracket(30., 315., 12.).unwrap();
+        assert_eq!(
+            on_grid.cases(),
+            vec!["zen30az045_CD12".parse::<CfdCase<2021>>().unwrap()]
+        );
+        assert!(on_grid.is_mirrored());
+        let stats: BTreeMap<_, _> = bracket
+            .cases()
+            .into_iter()
+            .map(|c| (c, f64::from(c.wind_speed)))
+            .collect();
+        assert!((bracket.combine_from(Parity::Odd, &stats).unwrap() - 9.).abs() < 1e-12);
+        let fx = |_| Ok::<f64, CfdError>(1.);
+        assert_eq!(on_grid.combine(Parity::FORCE[0], fx).unwrap(), -1.);
+        assert_eq!(on_grid.combine(Parity::FORCE[1], fx).unwrap(), 1.);
+
+        let baseline = Baseline::<2021>::default();
+        assert!(baseline.bracket(30., 45., 20.).unwrap().is_clamped());
+        assert!(!baseline.bracket(0., 0., 2.).unwrap().is_clamped());
+        assert!(matches!(
+            baseline.bracket(f64::NAN, 45., 7.),
+            Err(CfdError::CustomZenithAngle(_))
+        ));
+        assert!(matches!(
+            baseline.bracket(30., f64::INFINITY, 7.),
+            Err(CfdError::CustomAzimuth(_))
+        ));
+        assert!(matches!(
+            baseline.bracket(30., 45., f64::NAN),
+            Err(CfdError::CustomWindSpeed(_))
+        ));
+    }
+}
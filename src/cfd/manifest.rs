@@ -0,0 +1,181 @@
+//! # CFD baseline manifest
+//!
+//! A manifest is a TOML or JSON file with a user-defined list of CFD cases.
+//! The cases are given either by their directory names or as grids of
+//! zenith angles, azimuth angles and (wind speed, enclosure) pairs:
+//! ```toml
+//! cases = ["zen30az045_CD7", "zen30az135_CD7"]
+//!
+//! [[grid]]
+//! zenith = [30]
+//! azimuth = [0, 45, 90]
+//! configuration = [[2, "os"], [7, "os"], [12, "cd"]]
+//! ```
+//! If the configuration of a grid is omitted, the configuration of the baseline
+//! ([BaselineTrait::configuration]) for each zenith angle is used instead.
+
+use super::{Baseline, BaselineTrait, CfdCase, CfdError, Result, ZenithAngle};
+use crate::config::{self, Format};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, fs, path::Path, str::FromStr};
+
+/// CFD cases grid
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Grid {
+    /// Zenith angles [deg]
+    pub zenith: Vec<u32>,
+    /// Azimuth angles [deg]
+    pub azimuth: Vec<u32>,
+    /// Pairs of wind speed [m/s] and enclosure configuration ("os", "cd", ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub configuration: Vec<(u32, String)>,
+}
+impl Grid {
+    /// Expands the grid into [CfdCase]s
+    pub fn cases<const YEAR: u32>(&self) -> Result<Vec<CfdCase<YEAR>>>
+    where
+        Baseline<YEAR>: BaselineTrait<YEAR>,
+    {
+        let mut cfd_cases = vec![];
+        for &zenith in &self.zenith {
+            let configuration: Vec<(u32, String)> = if self.configuration.is_empty() {
+                Baseline::<YEAR>::configuration(ZenithAngle::new(zenith)?)
+                    .into_iter()
+                    .map(|(wind_speed, enclosure)| {
+                        (
+                            f64::from(wind_speed) as u32,
+                            enclosure.to_string().to_lowercase(),
+                        )
+                    })
+                    .collect()
+            } else {
+                self.configuration.clone()
+            };
+            for (wind_speed, enclosure) in &configuration {
+                for &azimuth in &self.azimuth {
+                    cfd_cases.push(CfdCase::colloquial(
+                        zenith,
+                        azimuth,
+                        enclosure,
+                        *wind_speed,
+                    )?);
+                }
+            }
+        }
+        Ok(cfd_cases)
+    }
+}
+
+/// CFD baseline manifest
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Manifest {
+    /// CFD cases directory names
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cases: Vec<String>,
+    /// CFD cases grids
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grid: Vec<Grid>,
+}
+impl Manifest {
+    /// Reads a manifest from a TOML (`.toml` extension) or a JSON (`.json` extension) file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(config::from_path(path)?)
+    }
+    /// Writes the manifest to a TOML (`.toml` extension) or a JSON (`.json` extension) file
+    pub fn to_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let contents = match Format::from_path(path)? {
+            Format::Toml => toml::to_string(self)?,
+            Format::Json => serde_json::to_string_pretty(self)?,
+        };
+        fs::write(path, contents).map_err(|e| CfdError::Manifest(e, path.to_path_buf()))
+    }
+}
+
+impl<const YEAR: u32> Baseline<YEAR>
+where
+    Baseline<YEAR>: BaselineTrait<YEAR>,
+    CfdCase<YEAR>: FromStr<Err = CfdError> + Display,
+{
+    /// Creates a baseline from the CFD cases listed in a [Manifest] file
+    ///
+    /// Cases that appear more than once are only kept once, in the order they are first found
+    pub fn from_manifest<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let manifest = Manifest::from_path(path)?;
+        let mut cfd_cases: Vec<CfdCase<YEAR>> = vec![];
+        let named_cases = manifest
+            .cases
+            .iter()
+            .map(|name| name.parse::<CfdCase<YEAR>>())
+            .collect::<Result<Vec<_>>>()?;
+        let grid_cases = manifest
+            .grid
+            .iter()
+            .map(|grid| grid.cases::<YEAR>())
+            .collect::<Result<Vec<_>>>()?;
+        for cfd_case in named_cases
+            .into_iter()
+            .chain(grid_cases.into_iter().flatten())
+        {
+            if !cfd_cases.contains(&cfd_case) {
+                cfd_cases.push(cfd_case);
+            }
+        }
+        Ok(cfd_cases.into())
+    }
+    /// Saves the baseline CFD cases into a [Manifest] file
+    pub fn to_manifest<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Manifest {
+            cases: self.0.iter().map(|cfd_case| cfd_case.to_string()).collect(),
+            ..Default::default()
+        }
+        .to_path(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_roundtrip() {
+        let toml = r#"
+cases = ["zen30az045_CD7", "zen30az000_OS2"]
+
+[[grid]]
+zenith = [30]
+azimuth = [0, 45]
+configuration = [[2, "os"], [12, "cd"]]
+
+[[grid]]
+zenith = [60]
+azimuth = [180]
+"#;
+        let dir = std::env::temp_dir().join(format!("manifest-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("manifest.toml");
+        fs::write(&path, toml).unwrap();
+        let baseline = Baseline::<2021>::from_manifest(&path).unwrap();
+        let names: Vec<String> = baseline.0.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "zen30az045_CD7",
+                "zen30az000_OS2",
+                "zen30az045_OS2",
+                "zen30az000_CD12",
+                "zen30az045_CD12",
+                "zen60az180_OS2",
+                "zen60az180_OS7",
+                "zen60az180_CS12",
+                "zen60az180_CS17",
+            ]
+        );
+        let path = path.with_extension("json");
+        baseline.to_manifest(&path).unwrap();
+        let reloaded = Baseline::<2021>::from_manifest(&path).unwrap();
+        assert_eq!(reloaded.0, baseline.0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -10,12 +10,26 @@ let cfd_cases_iter = cfd::Baseline::<2021>::default().into_iter();
 ```
 */
 
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::{
     env, fmt,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 use strum_macros::EnumIter;
 
+mod catalog;
+mod doctor;
+mod interpolation;
+mod manifest;
+mod query;
+pub use catalog::{CaseCatalog, DataFile, TimeStamps};
+pub use doctor::{CaseHealth, Doctor, HealthReport, SeriesHealth};
+pub use interpolation::{Bracket, Parity};
+pub use manifest::{Grid, Manifest};
+pub use query::{CaseQuery, CaseSelection};
+
 #[derive(thiserror::Error, Debug)]
 pub enum CfdError {
     #[error("zenith angle {0} is not recognized, expected 0, 30 or 60 degree")]
@@ -26,22 +40,83 @@ pub enum CfdError {
     Enclosure(String),
     #[error(r#"wind speed {0} is not recognized, expected 2, 7, 12m 17 or 22 m/s"#)]
     WindSpeed(u32),
+    #[error("zenith angle {0} is out of the [0,90] degree range")]
+    CustomZenithAngle(f64),
+    #[error("azimuth angle {0} is out of the [0,360[ degree range")]
+    CustomAzimuth(f64),
+    #[error("wind speed {0} is not a positive value")]
+    CustomWindSpeed(f64),
     #[error("Failed to read CFD data file")]
     ReadDataFile(#[from] glob::GlobError),
     #[error("Data file not recognized")]
     DataFileGlob(#[from] glob::PatternError),
     #[error("{0} data not available")]
     DataFile(String),
+    #[error("{0:?} is not a CFD case name")]
+    CaseName(String),
+    #[error("CFD repository root is not set, use `Baseline::with_root` or set {0:?}")]
+    Repository(String),
+    #[error("Failed to read or write the baseline manifest: {1:?}")]
+    Manifest(#[source] std::io::Error, PathBuf),
+    #[error("Failed to load the baseline manifest")]
+    Config(#[from] crate::config::ConfigError),
+    #[error("Failed to serialize the TOML baseline manifest")]
+    TomlSer(#[from] toml::ser::Error),
+    #[error("Failed to (de)serialize the JSON baseline manifest")]
+    Json(#[from] serde_json::Error),
+    #[error("no CFD case to interpolate from: {0}")]
+    Bracket(String),
+    #[error("invalid CFD cases query: {0}")]
+    Query(String),
 }
 
 type Result<T> = std::result::Result<T, CfdError>;
 
+/// Implements equality, hashing and ordering from the numerical value
+///
+/// Grid values and [custom](ZenithAngle::custom) values are compared by their value in `f64`
+macro_rules! impl_numeric_ord {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq for $t {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == std::cmp::Ordering::Equal
+                }
+            }
+            impl Eq for $t {}
+            impl std::hash::Hash for $t {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    // adding 0 turns -0 into +0
+                    (f64::from(*self) + 0f64).to_bits().hash(state);
+                }
+            }
+            impl PartialOrd for $t {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+            impl Ord for $t {
+                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    (f64::from(*self) + 0f64).total_cmp(&(f64::from(*other) + 0f64))
+                }
+            }
+        )*
+    };
+}
+impl_numeric_ord!(ZenithAngle, Azimuth, WindSpeed);
+
 /// CFD Telescope zenith pointing angle
-#[derive(EnumIter, Clone, Copy, PartialEq, Debug)]
+///
+/// (De)serialized as the angle in degree, see [ZenithAngle::custom]
+#[derive(EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
 pub enum ZenithAngle {
     Zero,
     Thirty,
     Sixty,
+    /// Off-grid zenith angle [deg]
+    #[strum(disabled)]
+    Custom(f64),
 }
 impl ZenithAngle {
     /// Get a new `ZenithAngle` chosen from 0, 30 or 60 degrees
@@ -54,6 +129,17 @@ impl ZenithAngle {
             _ => Err(CfdError::ZenithAngle(zenith_angle)),
         }
     }
+    /// Get a new `ZenithAngle` from any value within the \[0,90\] degree range
+    ///
+    /// The values of the CFD baseline grid (0, 30 and 60 degrees) are mapped to their named variants
+    pub fn custom(zenith_angle: f64) -> Result<Self> {
+        if !(0f64..=90f64).contains(&zenith_angle) {
+            return Err(CfdError::CustomZenithAngle(zenith_angle));
+        }
+        Ok(ZenithAngle::iter()
+            .find(|zen| f64::from(zen) == zenith_angle)
+            .unwrap_or(ZenithAngle::Custom(zenith_angle)))
+    }
     pub fn chapter_title(&self) -> String {
         let z: f64 = self.into();
         format!("Zenith angle: {} degree", z)
@@ -65,16 +151,20 @@ impl From<ZenithAngle> for f64 {
             ZenithAngle::Zero => 0f64,
             ZenithAngle::Thirty => 30f64,
             ZenithAngle::Sixty => 60f64,
+            ZenithAngle::Custom(zen) => zen,
         }
     }
 }
+impl TryFrom<f64> for ZenithAngle {
+    type Error = CfdError;
+
+    fn try_from(zenith_angle: f64) -> Result<Self> {
+        ZenithAngle::custom(zenith_angle)
+    }
+}
 impl From<&ZenithAngle> for f64 {
     fn from(zen: &ZenithAngle) -> Self {
-        match zen {
-            ZenithAngle::Zero => 0f64,
-            ZenithAngle::Thirty => 30f64,
-            ZenithAngle::Sixty => 60f64,
-        }
+        (*zen).into()
     }
 }
 impl fmt::Display for ZenithAngle {
@@ -83,17 +173,25 @@ impl fmt::Display for ZenithAngle {
             ZenithAngle::Zero => write!(f, "zen00"),
             ZenithAngle::Thirty => write!(f, "zen30"),
             ZenithAngle::Sixty => write!(f, "zen60"),
+            ZenithAngle::Custom(zen) if zen.fract() == 0f64 => write!(f, "zen{:02}", zen),
+            ZenithAngle::Custom(zen) => write!(f, "zen{}", zen),
         }
     }
 }
 /// CFD Telescope azimuth angle (wrt. NNE wind)
-#[derive(EnumIter, Clone, Copy, PartialEq, Debug)]
+///
+/// (De)serialized as the angle in degree, see [Azimuth::custom]
+#[derive(EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
 pub enum Azimuth {
     Zero,
     FortyFive,
     Ninety,
     OneThirtyFive,
     OneEighty,
+    /// Off-grid azimuth angle [deg]
+    #[strum(disabled)]
+    Custom(f64),
 }
 impl Azimuth {
     /// Get a new `Azimuth` chosen from 0, 45, 90, 135 or 180 degrees
@@ -108,6 +206,17 @@ impl Azimuth {
             _ => Err(CfdError::Azimuth(azimuth)),
         }
     }
+    /// Get a new `Azimuth` from any value within the \[0,360\[ degree range
+    ///
+    /// The values of the CFD baseline grid (0, 45, 90, 135 and 180 degrees) are mapped to their named variants
+    pub fn custom(azimuth: f64) -> Result<Self> {
+        if !(0f64..360f64).contains(&azimuth) {
+            return Err(CfdError::CustomAzimuth(azimuth));
+        }
+        Ok(Azimuth::iter()
+            .find(|azi| f64::from(azi) == azimuth)
+            .unwrap_or(Azimuth::Custom(azimuth)))
+    }
     pub fn sin_cos(&self) -> (f64, f64) {
         let v: f64 = self.into();
         v.to_radians().sin_cos()
@@ -122,19 +231,20 @@ impl From<Azimuth> for f64 {
             Ninety => 90f64,
             OneThirtyFive => 135f64,
             OneEighty => 180f64,
+            Custom(azi) => azi,
         }
     }
 }
+impl TryFrom<f64> for Azimuth {
+    type Error = CfdError;
+
+    fn try_from(azimuth: f64) -> Result<Self> {
+        Azimuth::custom(azimuth)
+    }
+}
 impl From<&Azimuth> for f64 {
     fn from(azi: &Azimuth) -> Self {
-        use Azimuth::*;
-        match azi {
-            Zero => 0f64,
-            FortyFive => 45f64,
-            Ninety => 90f64,
-            OneThirtyFive => 135f64,
-            OneEighty => 180f64,
-        }
+        (*azi).into()
     }
 }
 impl fmt::Display for Azimuth {
@@ -146,11 +256,13 @@ impl fmt::Display for Azimuth {
             Ninety => write!(f, "az090"),
             OneThirtyFive => write!(f, "az135"),
             OneEighty => write!(f, "az180"),
+            Custom(azi) if azi.fract() == 0f64 => write!(f, "az{:03}", azi),
+            Custom(azi) => write!(f, "az{}", azi),
         }
     }
 }
 /// Enclosure vents and wind screen configuration combinations
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub enum Enclosure {
     OpenStowed,
     NewMeshOpenStowed,
@@ -189,13 +301,19 @@ impl fmt::Display for Enclosure {
     }
 }
 /// CFD wind speed
-#[derive(EnumIter, Copy, PartialEq, Clone, Debug)]
+///
+/// (De)serialized as the speed in m/s, see [WindSpeed::custom]
+#[derive(EnumIter, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
 pub enum WindSpeed {
     Two,
     Seven,
     Twelve,
     Seventeen,
     TwentyTwo,
+    /// Off-grid wind speed [m/s]
+    #[strum(disabled)]
+    Custom(f64),
 }
 impl WindSpeed {
     /// Get a new `WindSpeed` chosen from 0, 2, 7, 12, 17 or 22m/s
@@ -210,6 +328,17 @@ impl WindSpeed {
             _ => Err(CfdError::WindSpeed(wind_speed)),
         }
     }
+    /// Get a new `WindSpeed` from any positive value in m/s
+    ///
+    /// The values of the CFD baseline grid (2, 7, 12, 17 and 22m/s) are mapped to their named variants
+    pub fn custom(wind_speed: f64) -> Result<Self> {
+        if !(wind_speed.is_finite() && wind_speed >= 0f64) {
+            return Err(CfdError::CustomWindSpeed(wind_speed));
+        }
+        Ok(WindSpeed::iter()
+            .find(|speed| f64::from(*speed) == wind_speed)
+            .unwrap_or(WindSpeed::Custom(wind_speed)))
+    }
 }
 impl fmt::Display for WindSpeed {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -220,6 +349,7 @@ impl fmt::Display for WindSpeed {
             Twelve => write!(f, "12"),
             Seventeen => write!(f, "17"),
             TwentyTwo => write!(f, "22"),
+            Custom(speed) => write!(f, "{}", speed),
         }
     }
 }
@@ -232,11 +362,19 @@ impl From<WindSpeed> for f64 {
             Twelve => 12,
             Seventeen => 17,
             TwentyTwo => 22,
+            Custom(speed) => return speed,
         } as f64)
     }
 }
+impl TryFrom<f64> for WindSpeed {
+    type Error = CfdError;
+
+    fn try_from(wind_speed: f64) -> Result<Self> {
+        WindSpeed::custom(wind_speed)
+    }
+}
 /// Data file collections available in the CFD database
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CfdDataFile<const YEAR: u32> {
     M1Pressure,
     M2Pressure,
@@ -244,7 +382,11 @@ pub enum CfdDataFile<const YEAR: u32> {
     OpticalPathDifference,
     TelescopePressure,
 }
-impl CfdDataFile<2021> {
+impl<const YEAR: u32> CfdDataFile<YEAR>
+where
+    CfdCase<YEAR>: Is2021or2025,
+    Baseline<YEAR>: BaselineTrait<YEAR>,
+{
     pub fn pattern(self) -> String {
         use CfdDataFile::*;
         String::from(match self {
@@ -255,9 +397,10 @@ impl CfdDataFile<2021> {
             TelescopePressure => "Telescope_p_table_",
         })
     }
-    pub fn glob(self, cfd_case: CfdCase<2021>) -> Result<Vec<PathBuf>> {
+    /// Returns the data files of a CFD case within the given baseline repository
+    pub fn glob(self, baseline: &Baseline<YEAR>, cfd_case: CfdCase<YEAR>) -> Result<Vec<PathBuf>> {
         use CfdDataFile::*;
-        let cfd_path = Baseline::<2021>::path().join(cfd_case.to_string());
+        let cfd_path = baseline.case_path(cfd_case)?;
         let paths = match self {
             M1Pressure => glob::glob(
                 cfd_path
@@ -299,12 +442,14 @@ impl CfdDataFile<2021> {
     }
 }
 impl CfdDataFile<2020> {
+    /// Returns the data files of a CFD case within the given baseline repository
     pub fn glob(
         self,
-        cfd_case: CfdCase<2021>,
+        baseline: &Baseline<2020>,
+        cfd_case: CfdCase<2020>,
     ) -> std::result::Result<impl Iterator<Item = glob::GlobResult>, CfdError> {
         use CfdDataFile::*;
-        let cfd_path = Baseline::<2021>::path().join(cfd_case.to_string());
+        let cfd_path = baseline.case_path(cfd_case)?;
         match self {
             M1Pressure => Ok(glob::glob(
                 cfd_path.join("M1_data_Mod_M1_Data_*.csv").to_str().unwrap(),
@@ -323,8 +468,13 @@ impl CfdDataFile<2020> {
     }
 }
 
-/// CFD case for a given year: 2020 or 2021
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// Marker for the CFD campaigns sharing the 2021 database layout: 2021 and 2025
+pub trait Is2021or2025 {}
+impl Is2021or2025 for CfdCase<2021> {}
+impl Is2021or2025 for CfdCase<2025> {}
+
+/// CFD case for a given year: 2020, 2021 or 2025
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct CfdCase<const YEAR: u32> {
     pub zenith: ZenithAngle,
     pub azimuth: Azimuth,
@@ -360,6 +510,23 @@ impl<const YEAR: u32> CfdCase<YEAR> {
             WindSpeed::new(wind_speed)?,
         ))
     }
+    /// A new CFD case with any zenith and azimuth angles [deg] and wind speed [m/s]
+    ///
+    /// Values on the CFD baseline grid are mapped to the named variants of [ZenithAngle], [Azimuth] and [WindSpeed],
+    /// it will return an error if the values are out of range (see [ZenithAngle::custom], [Azimuth::custom] and [WindSpeed::custom])
+    pub fn custom(
+        zenith_angle: f64,
+        azimuth: f64,
+        enclosure: &str,
+        wind_speed: f64,
+    ) -> Result<Self> {
+        Ok(CfdCase::<YEAR>::new(
+            ZenithAngle::custom(zenith_angle)?,
+            Azimuth::custom(azimuth)?,
+            Enclosure::new(enclosure)?,
+            WindSpeed::custom(wind_speed)?,
+        ))
+    }
     /// Pretty print the CFD case
     pub fn to_pretty_string(&self) -> String {
         let z: f64 = self.zenith.clone().into();
@@ -385,7 +552,10 @@ impl<const YEAR: u32> CfdCase<YEAR> {
         )
     }
 }
-impl fmt::Display for CfdCase<2021> {
+impl<const YEAR: u32> fmt::Display for CfdCase<YEAR>
+where
+    CfdCase<YEAR>: Is2021or2025,
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -408,31 +578,107 @@ impl fmt::Display for CfdCase<2020> {
         )
     }
 }
-/// The whole CFD baseline  for a given year: 2020 or 2021
-#[derive(Debug)]
-pub struct Baseline<const YEAR: u32>(Vec<CfdCase<YEAR>>);
+impl<const YEAR: u32> FromStr for CfdCase<YEAR>
+where
+    CfdCase<YEAR>: Is2021or2025,
+{
+    type Err = CfdError;
+
+    /// Parses a CFD case from its directory name e.g. `zen30az045_OS7`
+    ///
+    /// The name may be embedded in a longer string, like the path to a file of the CFD case
+    fn from_str(s: &str) -> Result<Self> {
+        let re =
+            Regex::new(r"zen(\d+(?:\.\d+)?)az(\d+(?:\.\d+)?)_([A-Z]+)(\d+(?:\.\d+)?)").unwrap();
+        let capts = re
+            .captures(s)
+            .ok_or_else(|| CfdError::CaseName(s.to_string()))?;
+        CfdCase::custom(
+            capts[1].parse().unwrap(),
+            capts[2].parse().unwrap(),
+            &capts[3].to_lowercase(),
+            capts[4].parse().unwrap(),
+        )
+    }
+}
+impl FromStr for CfdCase<2020> {
+    type Err = CfdError;
+
+    /// Parses a CFD case from its directory name e.g. `b2019_30z_45az_os_7ms`
+    ///
+    /// The name may be embedded in a longer string, like the path to a file of the CFD case
+    fn from_str(s: &str) -> Result<Self> {
+        let re = Regex::new(r"b2019_(\d+(?:\.\d+)?)z_(\d+(?:\.\d+)?)az_([a-z]+)_(\d+(?:\.\d+)?)ms")
+            .unwrap();
+        let capts = re
+            .captures(s)
+            .ok_or_else(|| CfdError::CaseName(s.to_string()))?;
+        CfdCase::custom(
+            capts[1].parse().unwrap(),
+            capts[2].parse().unwrap(),
+            &capts[3],
+            capts[4].parse().unwrap(),
+        )
+    }
+}
+/// The whole CFD baseline  for a given year: 2020, 2021 or 2025
+///
+/// The CFD cases are located in the repository given by [Baseline::path]
+#[derive(Debug, Clone)]
+pub struct Baseline<const YEAR: u32>(Vec<CfdCase<YEAR>>, Option<PathBuf>);
 impl<const YEAR: u32> From<Vec<CfdCase<YEAR>>> for Baseline<YEAR> {
     fn from(cfd_cases: Vec<CfdCase<YEAR>>) -> Self {
-        Baseline::<YEAR>(cfd_cases)
+        Baseline::<YEAR>(cfd_cases, None)
+    }
+}
+impl<const YEAR: u32> Baseline<YEAR>
+where
+    Baseline<YEAR>: BaselineTrait<YEAR>,
+{
+    /// Sets the path to the CFD cases repository
+    pub fn with_root<P: AsRef<Path>>(self, root: P) -> Self {
+        Self(self.0, Some(root.as_ref().to_path_buf()))
+    }
+    /// Returns the path to the CFD cases repository
+    ///
+    /// The path is either the one set with [Baseline::with_root] or [BaselineTrait::default_path]
+    pub fn path(&self) -> Result<PathBuf> {
+        match &self.1 {
+            Some(root) => Ok(root.clone()),
+            None => Self::default_path(),
+        }
+    }
+    /// Returns the path to a CFD case
+    pub fn case_path(&self, cfd_case: CfdCase<YEAR>) -> Result<PathBuf>
+    where
+        CfdCase<YEAR>: fmt::Display,
+    {
+        Ok(self.path()?.join(cfd_case.to_string()))
     }
 }
 use strum::IntoEnumIterator;
 impl Default for Baseline<2020> {
     fn default() -> Self {
-        Self(
-            ZenithAngle::iter()
-                .flat_map(|zenith_angle| Self::at_zenith(zenith_angle).0)
-                .collect(),
-        )
+        ZenithAngle::iter()
+            .flat_map(|zenith_angle| Self::at_zenith(zenith_angle).0)
+            .collect::<Vec<_>>()
+            .into()
     }
 }
 impl Default for Baseline<2021> {
     fn default() -> Self {
-        Self(
-            ZenithAngle::iter()
-                .flat_map(|zenith_angle| Self::at_zenith(zenith_angle).0)
-                .collect(),
-        )
+        ZenithAngle::iter()
+            .flat_map(|zenith_angle| Self::at_zenith(zenith_angle).0)
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+impl Default for Baseline<2025> {
+    fn default() -> Self {
+        ZenithAngle::iter()
+            .flat_map(|zenith_angle| Self::at_zenith(zenith_angle).0)
+            .collect::<Vec<_>>()
+            .into()
     }
 }
 impl<const YEAR: u32> IntoIterator for Baseline<YEAR> {
@@ -475,13 +721,13 @@ pub trait BaselineTrait<const YEAR: u32>:
     Default + From<Vec<CfdCase<YEAR>>> + IntoIterator<Item = CfdCase<YEAR>>
 {
     /// Returns the default path to the CFD cases repository
-    // fn path() -> PathBuf;
-    /// Return the path from the "CFD_REPO" environment variable if it is set,
-    /// otherwise returns the default path
-    fn path() -> PathBuf {
+    ///
+    /// The path is read from the "CFD_REPO" environment variable,
+    /// an error is returned if it is not set
+    fn default_path() -> Result<PathBuf> {
         env::var("CFD_REPO")
-            .map(|p| Path::new(&p).to_path_buf())
-            .expect(r#""CFD_REPO" is not set"#)
+            .map(PathBuf::from)
+            .map_err(|_| CfdError::Repository("CFD_REPO".into()))
     }
     /// Returns pairs of [WindSpeed] and [Enclosure] configuration for the given [ZenithAngle]
     fn configuration(zenith_angle: ZenithAngle) -> Vec<(WindSpeed, Enclosure)>;
@@ -501,7 +747,19 @@ pub trait BaselineTrait<const YEAR: u32>:
         cfd_cases.into()
     }
     /// Finds the CFD case from `OTHER_YEAR` that matches a CFD baseline case in `YEAR`
+    ///
+    /// A case with the same zenith angle, azimuth angle, wind speed and enclosure is looked for first,
+    /// otherwise, at 60 degree zenith angle and 12 or 17m/s wind speed, the closed deployed case is returned
     fn find<const OTHER_YEAR: u32>(cfd_case_21: CfdCase<OTHER_YEAR>) -> Option<CfdCase<YEAR>> {
+        let same = |cfd_case: &CfdCase<YEAR>| {
+            cfd_case.zenith == cfd_case_21.zenith
+                && cfd_case.azimuth == cfd_case_21.azimuth
+                && cfd_case.wind_speed == cfd_case_21.wind_speed
+                && cfd_case.enclosure == cfd_case_21.enclosure
+        };
+        if let Some(cfd_case) = Self::default().into_iter().find(same) {
+            return Some(cfd_case);
+        }
         Self::default().into_iter().find(|cfd_case_20| {
             match (cfd_case_21.zenith.clone(), cfd_case_21.wind_speed.clone()) {
                 (ZenithAngle::Sixty, WindSpeed::Twelve | WindSpeed::Seventeen) => {
@@ -521,9 +779,11 @@ pub trait BaselineTrait<const YEAR: u32>:
     }
 }
 impl BaselineTrait<2020> for Baseline<2020> {
-    fn path() -> PathBuf {
+    fn default_path() -> Result<PathBuf> {
         // Path::new("/fsx/Baseline2020").to_path_buf()
-        Path::new(&env::var("CFD_REPO_2020").expect("CFD_REPO_2020 is not set")).to_path_buf()
+        env::var("CFD_REPO_2020")
+            .map(PathBuf::from)
+            .map_err(|_| CfdError::Repository("CFD_REPO_2020".into()))
     }
 
     fn configuration(_: ZenithAngle) -> Vec<(WindSpeed, Enclosure)> {
@@ -536,12 +796,6 @@ impl BaselineTrait<2020> for Baseline<2020> {
     }
 }
 impl BaselineTrait<2021> for Baseline<2021> {
-    fn path() -> PathBuf {
-        env::var("CFD_REPO")
-            .map(|p| Path::new(&p).to_path_buf())
-            .expect(r#""CFD_REPO is not set""#)
-    }
-
     fn configuration(zenith_angle: ZenithAngle) -> Vec<(WindSpeed, Enclosure)> {
         match zenith_angle {
             ZenithAngle::Sixty => vec![
@@ -561,10 +815,24 @@ impl BaselineTrait<2021> for Baseline<2021> {
         }
     }
 }
+impl BaselineTrait<2025> for Baseline<2025> {
+    /// Returns the default path to the 2025 CFD cases repository
+    ///
+    /// The path is read from the "CFD_REPO_2025" environment variable
+    fn default_path() -> Result<PathBuf> {
+        env::var("CFD_REPO_2025")
+            .map(PathBuf::from)
+            .map_err(|_| CfdError::Repository("CFD_REPO_2025".into()))
+    }
+
+    fn configuration(zenith_angle: ZenithAngle) -> Vec<(WindSpeed, Enclosure)> {
+        Baseline::<2021>::configuration(zenith_angle)
+    }
+}
 impl Baseline<2021> {
     /// Mount cases
     pub fn mount() -> Self {
-        Self(
+        Self::from(
             WindSpeed::iter()
                 .take(3)
                 .filter_map(|wind_speed| match wind_speed {
@@ -615,7 +883,7 @@ impl Baseline<2021> {
     }
     /// REDO cases
     pub fn redo() -> Self {
-        Self(vec![
+        Self::from(vec![
             CfdCase::new(
                 ZenithAngle::Zero,
                 Azimuth::Ninety,
@@ -638,7 +906,7 @@ impl Baseline<2021> {
     }
     /// REDO cases
     pub fn thbound2() -> Self {
-        Self(vec![
+        Self::from(vec![
             CfdCase::new(
                 ZenithAngle::Thirty,
                 Azimuth::FortyFive,
@@ -682,6 +950,111 @@ impl Baseline<2021> {
                 WindSpeed::TwentyTwo,
             ),
         ]);
-        Self(cases)
+        Self(cases, self.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_name_roundtrip() {
+        for cfd_case in Baseline::<2021>::default().into_iter() {
+            assert_eq!(
+                cfd_case.to_string().parse::<CfdCase<2021>>().unwrap(),
+                cfd_case
+            );
+        }
+        for cfd_case in Baseline::<2020>::default().into_iter() {
+            assert_eq!(
+                cfd_case.to_string().parse::<CfdCase<2020>>().unwrap(),
+                cfd_case
+            );
+        }
+    }
+    #[test]
+    fn case_from_path() {
+        let cfd_case: CfdCase<2021> = "/fsx/CASES/zen60az135_CS17/monitors.csv.z".parse().unwrap();
+        assert_eq!(cfd_case, CfdCase::colloquial(60, 135, "cs", 17).unwrap());
+        assert!("zen30az000_XX7".parse::<CfdCase<2021>>().is_err());
+        assert!("b2019_30z_0az_os_7ms".parse::<CfdCase<2021>>().is_err());
+    }
+    #[test]
+    fn off_grid_case() {
+        let cfd_case = CfdCase::<2021>::custom(45., 22.5, "os", 10.).unwrap();
+        assert_eq!(cfd_case.zenith, ZenithAngle::Custom(45.));
+        assert_eq!(cfd_case.to_string(), "zen45az22.5_OS10");
+        assert_eq!(
+            cfd_case.to_string().parse::<CfdCase<2021>>().unwrap(),
+            cfd_case
+        );
+        assert_eq!(
+            cfd_case.to_pretty_string(),
+            "45 deg zenith - 22.5 deg azimuth - Open vents/Stowed wind screen - 10m/s"
+        );
+        assert_eq!(cfd_case.to_latex_string(), " 45 & 22.5 & os & 10");
+        assert_eq!(
+            CfdCase::<2021>::custom(5., 9., "cd", 2.)
+                .unwrap()
+                .to_string(),
+            "zen05az009_CD2"
+        );
+        let on_grid = CfdCase::<2021>::custom(30., 45., "cd", 12.).unwrap();
+        assert_eq!(on_grid.azimuth, Azimuth::FortyFive);
+        assert_eq!(on_grid, CfdCase::colloquial(30, 45, "cd", 12).unwrap());
+        assert!(CfdCase::<2021>::custom(30., -45., "os", 7.).is_err());
+        assert!(CfdCase::<2021>::custom(95., 45., "os", 7.).is_err());
+        assert!(CfdCase::<2021>::custom(30., 45., "os", f64::NAN).is_err());
+        assert!(ZenithAngle::Thirty < ZenithAngle::Custom(45.));
+        assert!(ZenithAngle::Custom(45.) < ZenithAngle::Sixty);
+    }
+    #[test]
+    fn custom_serde() {
+        assert_eq!(
+            serde_json::from_str::<ZenithAngle>("30.0").unwrap(),
+            ZenithAngle::Thirty
+        );
+        assert_eq!(
+            serde_json::from_str::<Azimuth>("22.5").unwrap(),
+            Azimuth::Custom(22.5)
+        );
+        assert_eq!(
+            serde_json::from_str::<WindSpeed>("12").unwrap(),
+            WindSpeed::Twelve
+        );
+        assert_eq!(serde_json::to_string(&ZenithAngle::Sixty).unwrap(), "60.0");
+        #[derive(Debug, Deserialize)]
+        struct Case {
+            #[allow(dead_code)]
+            zenith: ZenithAngle,
+        }
+        assert!(toml::from_str::<Case>("zenith = nan").is_err());
+        assert!(serde_json::from_str::<WindSpeed>("-7.0").is_err());
+    }
+    #[test]
+    fn baseline_2025() {
+        for cfd_case in Baseline::<2025>::default().into_iter() {
+            let name = cfd_case.to_string();
+            assert_eq!(name.parse::<CfdCase<2025>>().unwrap(), cfd_case);
+            let cfd_case_21 = Baseline::<2021>::find(cfd_case).unwrap();
+            assert_eq!(cfd_case_21.to_string(), name);
+        }
+        let cfd_case = CfdCase::<2025>::colloquial(60, 90, "cs", 12).unwrap();
+        assert_eq!(
+            Baseline::<2020>::find(cfd_case).unwrap(),
+            CfdCase::<2020>::colloquial(60, 90, "cd", 12).unwrap()
+        );
+    }
+    #[test]
+    fn baseline_root() {
+        let cfd_case = CfdCase::<2021>::colloquial(30, 45, "cd", 12).unwrap();
+        let baseline = Baseline::<2021>::default().with_root("/data/CASES");
+        assert_eq!(
+            baseline.case_path(cfd_case).unwrap(),
+            Path::new("/data/CASES/zen30az045_CD12")
+        );
+        let baseline = Baseline::<2021>::redo().with_root("/data/REDO").extras();
+        assert_eq!(baseline.path().unwrap(), Path::new("/data/REDO"));
     }
 }
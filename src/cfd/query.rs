@@ -0,0 +1,249 @@
+//! # CFD cases selection
+//!
+//! A [CaseQuery] is a filter expression on the CFD cases parameters, e.g.
+//! ```text
+//! zen=30 & az in [0,45] & wind>=12 & enc=cd
+//! ```
+//! The conditions are separated by `&` and all must be satisfied.
+//! A condition is made of a parameter:
+//!  - `zen` or `zenith`: zenith angle [deg]
+//!  - `az` or `azimuth`: azimuth angle [deg]
+//!  - `wind` or `ws`: wind speed [m/s]
+//!  - `enc` or `enclosure`: enclosure configuration (`os`, `nos`, `cd` or `cs`)
+//!
+//! an operator (`=`, `!=`, `<`, `<=`, `>`, `>=` or `in`) and a value or a list of values within brackets for `in`.
+//! The enclosure only supports the `=`, `!=` and `in` operators.
+//!
+//! [CaseSelection] gathers the command line options shared by the binaries to select CFD cases
+//! from a [Manifest](super::Manifest) and a [CaseQuery].
+
+use super::{Baseline, BaselineTrait, CfdCase, CfdError, Enclosure, Result};
+use regex::Regex;
+use std::{fmt::Display, str::FromStr};
+use structopt::StructOpt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Parameter {
+    Zenith,
+    Azimuth,
+    WindSpeed,
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Numeric(Parameter, Operator, Vec<f64>),
+    Enclosure(Operator, Vec<Enclosure>),
+}
+impl Condition {
+    fn matches<const YEAR: u32>(&self, cfd_case: &CfdCase<YEAR>) -> bool {
+        match self {
+            Condition::Numeric(parameter, operator, values) => {
+                let x = match parameter {
+                    Parameter::Zenith => f64::from(cfd_case.zenith),
+                    Parameter::Azimuth => f64::from(cfd_case.azimuth),
+                    Parameter::WindSpeed => f64::from(cfd_case.wind_speed),
+                };
+                let v = values[0];
+                match operator {
+                    Operator::Eq => x == v,
+                    Operator::Ne => x != v,
+                    Operator::Lt => x < v,
+                    Operator::Le => x <= v,
+                    Operator::Gt => x > v,
+                    Operator::Ge => x >= v,
+                    Operator::In => values.contains(&x),
+                }
+            }
+            Condition::Enclosure(operator, values) => match operator {
+                Operator::Ne => !values.contains(&cfd_case.enclosure),
+                _ => values.contains(&cfd_case.enclosure),
+            },
+        }
+    }
+}
+impl FromStr for Condition {
+    type Err = CfdError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let err = |msg: &str| CfdError::Query(format!("{msg} in {s:?}"));
+        let re = Regex::new(r"^\s*([a-z_]+)\s*(==|=|!=|<=|>=|<|>|in\b)\s*(.+?)\s*$").unwrap();
+        let capts = re
+            .captures(s)
+            .ok_or_else(|| err("expected `<parameter> <operator> <value>`"))?;
+        let operator = match &capts[2] {
+            "=" | "==" => Operator::Eq,
+            "!=" => Operator::Ne,
+            "<" => Operator::Lt,
+            "<=" => Operator::Le,
+            ">" => Operator::Gt,
+            ">=" => Operator::Ge,
+            _ => Operator::In,
+        };
+        let values: Vec<&str> = if operator == Operator::In {
+            capts[3]
+                .strip_prefix('[')
+                .and_then(|v| v.strip_suffix(']'))
+                .ok_or_else(|| err("expected a list of values within brackets"))?
+                .split(',')
+                .map(|v| v.trim())
+                .collect()
+        } else {
+            vec![&capts[3]]
+        };
+        let parameter = match &capts[1] {
+            "zen" | "zenith" => Parameter::Zenith,
+            "az" | "azimuth" => Parameter::Azimuth,
+            "wind" | "ws" | "wind_speed" => Parameter::WindSpeed,
+            "enc" | "enclosure" => {
+                if !matches!(operator, Operator::Eq | Operator::Ne | Operator::In) {
+                    return Err(err(
+                        "the enclosure can only be compared with `=`, `!=` or `in`",
+                    ));
+                }
+                return Ok(Condition::Enclosure(
+                    operator,
+                    values
+                        .into_iter()
+                        .map(|v| Enclosure::new(&v.to_lowercase()))
+                        .collect::<Result<Vec<_>>>()?,
+                ));
+            }
+            _ => return Err(err("unknown parameter")),
+        };
+        Ok(Condition::Numeric(
+            parameter,
+            operator,
+            values
+                .into_iter()
+                .map(|v| v.parse::<f64>().map_err(|_| err("expected a number")))
+                .collect::<Result<Vec<_>>>()?,
+        ))
+    }
+}
+
+/// CFD cases filter expression
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaseQuery(Vec<Condition>);
+impl FromStr for CaseQuery {
+    type Err = CfdError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self(
+            s.split('&')
+                .filter(|c| !c.trim().is_empty())
+                .map(|c| c.parse())
+                .collect::<Result<Vec<_>>>()?,
+        ))
+    }
+}
+impl CaseQuery {
+    /// Returns true if the CFD case satisfies all the conditions of the query
+    pub fn matches<const YEAR: u32>(&self, cfd_case: &CfdCase<YEAR>) -> bool {
+        self.0.iter().all(|condition| condition.matches(cfd_case))
+    }
+}
+
+impl<const YEAR: u32> Baseline<YEAR> {
+    /// Returns the CFD cases of the baseline that match the query
+    pub fn select(self, query: &CaseQuery) -> Self {
+        Self(
+            self.0.into_iter().filter(|c| query.matches(c)).collect(),
+            self.1,
+        )
+    }
+}
+
+/// Command line options to select CFD cases
+///
+/// It is meant to be flattened into the command line options of the binaries:
+/// ```no_run
+/// use parse_monitors::cfd::CaseSelection;
+/// use structopt::StructOpt;
+///
+/// #[derive(StructOpt)]
+/// struct Opt {
+///     #[structopt(flatten)]
+///     cases: CaseSelection,
+/// }
+/// let opt = Opt::from_args();
+/// let baseline = opt.cases.baseline::<2021>().unwrap();
+/// ```
+#[derive(Debug, Clone, Default, StructOpt)]
+pub struct CaseSelection {
+    /// Baseline manifest file (TOML or JSON)
+    #[structopt(long)]
+    pub baseline: Option<String>,
+    /// CFD cases filter, e.g. "zen=30 & az in [0,45] & wind>=12 & enc=cd"
+    #[structopt(long)]
+    pub cases: Option<CaseQuery>,
+}
+impl CaseSelection {
+    /// Returns the CFD cases from the manifest, or else from `baseline`, that match the query
+    pub fn select<const YEAR: u32>(&self, baseline: Baseline<YEAR>) -> Result<Baseline<YEAR>>
+    where
+        Baseline<YEAR>: BaselineTrait<YEAR>,
+        CfdCase<YEAR>: FromStr<Err = CfdError> + Display,
+    {
+        let baseline = match &self.baseline {
+            Some(manifest) => Baseline::<YEAR>::from_manifest(manifest)?,
+            None => baseline,
+        };
+        Ok(match &self.cases {
+            Some(query) => baseline.select(query),
+            None => baseline,
+        })
+    }
+    /// Returns the CFD cases from the manifest, or else from the default baseline, that match the query
+    pub fn baseline<const YEAR: u32>(&self) -> Result<Baseline<YEAR>>
+    where
+        Baseline<YEAR>: BaselineTrait<YEAR>,
+        CfdCase<YEAR>: FromStr<Err = CfdError> + Display,
+    {
+        self.select(Baseline::<YEAR>::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query() {
+        let query: CaseQuery = "zen=30 & az in [0,45] & wind>=12 & enc=cd".parse().unwrap();
+        let names: Vec<String> = Baseline::<2021>::default()
+            .select(&query)
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "zen30az000_CD12",
+                "zen30az045_CD12",
+                "zen30az000_CD17",
+                "zen30az045_CD17"
+            ]
+        );
+        let query: CaseQuery = "zenith != 30&enc in [OS, cs] & az<45".parse().unwrap();
+        assert_eq!(
+            Baseline::<2021>::default()
+                .select(&query)
+                .into_iter()
+                .count(),
+            6
+        );
+        assert!("zen~30".parse::<CaseQuery>().is_err());
+        assert!("enc>cd".parse::<CaseQuery>().is_err());
+        assert!("az in 0,45".parse::<CaseQuery>().is_err());
+        assert!("wind=fast".parse::<CaseQuery>().is_err());
+    }
+}
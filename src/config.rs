@@ -0,0 +1,83 @@
+//! TOML and JSON input files
+//!
+//! The monitor groups, the frames, the FEM nodes and the baseline manifests are read from TOML or JSON files.
+//! The format of a file is given by its extension, `.toml` or `.json`, any other extension is an error.
+
+use serde::de::DeserializeOwned;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read {1:?}")]
+    Io(#[source] io::Error, PathBuf),
+    #[error("failed to parse the TOML file {1:?}")]
+    Toml(#[source] toml::de::Error, PathBuf),
+    #[error("failed to parse the JSON file {1:?}")]
+    Json(#[source] serde_json::Error, PathBuf),
+    #[error("unknown file format {0:?}, expected a toml or json file")]
+    Format(PathBuf),
+}
+type Result<T> = std::result::Result<T, ConfigError>;
+
+/// Input file format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Toml,
+    Json,
+}
+impl Format {
+    /// Returns the format of a file from its extension
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Ok(Format::Toml),
+            Some("json") => Ok(Format::Json),
+            _ => Err(ConfigError::Format(path.to_path_buf())),
+        }
+    }
+}
+
+/// Deserializes a TOML or a JSON file
+pub fn from_path<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
+    let path = path.as_ref();
+    let format = Format::from_path(path)?;
+    let contents = fs::read_to_string(path).map_err(|e| ConfigError::Io(e, path.to_path_buf()))?;
+    match format {
+        Format::Toml => {
+            toml::from_str(&contents).map_err(|e| ConfigError::Toml(e, path.to_path_buf()))
+        }
+        Format::Json => {
+            serde_json::from_str(&contents).map_err(|e| ConfigError::Json(e, path.to_path_buf()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn formats() {
+        let dir = std::env::temp_dir().join(format!("config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.toml"), "x = 1.0\n").unwrap();
+        fs::write(dir.join("a.json"), r#"{"x": 1.0}"#).unwrap();
+        fs::write(dir.join("a.yaml"), "x: 1.0\n").unwrap();
+        let toml: BTreeMap<String, f64> = from_path(dir.join("a.toml")).unwrap();
+        let json: BTreeMap<String, f64> = from_path(dir.join("a.json")).unwrap();
+        assert_eq!(toml, json);
+        assert!(matches!(
+            from_path::<BTreeMap<String, f64>, _>(dir.join("a.yaml")),
+            Err(ConfigError::Format(_))
+        ));
+        assert!(matches!(
+            from_path::<BTreeMap<String, f64>, _>(dir.join("b.toml")),
+            Err(ConfigError::Io(..))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+//! Decompression of the CFD data files
+//!
+//! The compression format of a file is detected from its first bytes (magic number)
+//! or, if the magic number is not recognized, from the file extension.
+//! Files that are neither gzip, bzip2 nor zstd compressed are read as is.
+//!
+//! The bzip2 and zstd decoders require the `bzip2` and `zstd` features, respectively.
+//!
+//! ```no_run
+//! use parse_monitors::decompress;
+//! use std::io::Read;
+//!
+//! // any of monitors.csv.z, monitors.csv.bz2, monitors.csv.zst or monitors.csv
+//! let path = decompress::find("monitors.csv", decompress::CSV_EXTENSIONS).unwrap();
+//! let mut contents = String::new();
+//! decompress::open(&path).unwrap().read_to_string(&mut contents).unwrap();
+//! ```
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Extensions of the CSV data files, in order of preference
+pub const CSV_EXTENSIONS: &[&str] = &["csv.z", "csv.gz", "csv.bz2", "csv.zst", "csv"];
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecompressError {
+    #[error("no file found for {0:?}, tried: {1}")]
+    NotFound(PathBuf, String),
+    #[error("failed to open {0:?}")]
+    Open(#[source] io::Error, PathBuf),
+    #[error("failed to read {0:?} as {1}")]
+    Read(#[source] io::Error, PathBuf, Compression),
+    #[error("{0:?} is {1} compressed but the `{2}` feature is not enabled")]
+    Unsupported(PathBuf, Compression, &'static str),
+}
+type Result<T> = std::result::Result<T, DecompressError>;
+
+/// Compression formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Bzip2,
+    Zstd,
+    /// Uncompressed
+    None,
+}
+impl Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compression::Gzip => write!(f, "gzip"),
+            Compression::Bzip2 => write!(f, "bzip2"),
+            Compression::Zstd => write!(f, "zstd"),
+            Compression::None => write!(f, "uncompressed"),
+        }
+    }
+}
+impl Compression {
+    /// Compression format from the magic number at the beginning of a file
+    pub fn from_magic(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0x1f, 0x8b, ..] => Some(Compression::Gzip),
+            [b'B', b'Z', b'h', ..] => Some(Compression::Bzip2),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+    /// Compression format from the file extension
+    pub fn from_extension<P: AsRef<Path>>(path: P) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "z" | "gz" => Some(Compression::Gzip),
+            "bz2" => Some(Compression::Bzip2),
+            "zst" | "zstd" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+    /// Compression format of a file from its magic number, or else from its extension
+    ///
+    /// Defaults to [Compression::None]
+    pub fn detect<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut file = File::open(path).map_err(|e| DecompressError::Open(e, path.into()))?;
+        let mut magic = [0u8; 4];
+        let n =
+            read_magic(&mut file, &mut magic).map_err(|e| DecompressError::Open(e, path.into()))?;
+        Ok(Self::from_magic(&magic[..n])
+            .or_else(|| Self::from_extension(path))
+            .unwrap_or(Compression::None))
+    }
+}
+fn read_magic<R: Read>(reader: &mut R, magic: &mut [u8]) -> io::Result<usize> {
+    let mut n = 0;
+    while n < magic.len() {
+        match reader.read(&mut magic[n..])? {
+            0 => break,
+            m => n += m,
+        }
+    }
+    Ok(n)
+}
+
+/// Returns the first existing file made of the stem of `path` with one of the `extensions`,
+/// or else `path` if it exists
+pub fn find<P: AsRef<Path>>(path: P, extensions: &[&str]) -> Result<PathBuf> {
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let stem = extensions
+        .iter()
+        .find_map(|extension| name.strip_suffix(&format!(".{extension}")))
+        .unwrap_or(name);
+    extensions
+        .iter()
+        .map(|extension| path.with_file_name(format!("{stem}.{extension}")))
+        .chain(Some(path.to_path_buf()))
+        .find(|path| path.is_file())
+        .ok_or_else(|| DecompressError::NotFound(path.to_path_buf(), extensions.join(", ")))
+}
+
+/// Opens a file and returns a reader on the decompressed contents
+pub fn open<P: AsRef<Path>>(path: P) -> Result<Box<dyn Read>> {
+    let path = path.as_ref();
+    let compression = Compression::detect(path)?;
+    let file = File::open(path).map_err(|e| DecompressError::Open(e, path.into()))?;
+    let buf = BufReader::new(file);
+    decoder(buf, path, compression)
+}
+fn decoder<R: BufRead + 'static>(
+    buf: R,
+    path: &Path,
+    compression: Compression,
+) -> Result<Box<dyn Read>> {
+    match compression {
+        Compression::Gzip => Ok(Box::new(flate2::bufread::MultiGzDecoder::new(buf))),
+        #[cfg(feature = "bzip2")]
+        Compression::Bzip2 => Ok(Box::new(bzip2::bufread::MultiBzDecoder::new(buf))),
+        #[cfg(not(feature = "bzip2"))]
+        Compression::Bzip2 => Err(DecompressError::Unsupported(
+            path.into(),
+            compression,
+            "bzip2",
+        )),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => zstd::stream::read::Decoder::with_buffer(buf)
+            .map(|decoder| Box::new(decoder) as Box<dyn Read>)
+            .map_err(|e| DecompressError::Read(e, path.into(), compression)),
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => Err(DecompressError::Unsupported(
+            path.into(),
+            compression,
+            "zstd",
+        )),
+        Compression::None => Ok(Box::new(buf)),
+    }
+}
+
+/// Reads the decompressed contents of a file into a string
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    let mut contents = String::new();
+    open(path)?.read_to_string(&mut contents).map_err(|e| {
+        DecompressError::Read(
+            e,
+            path.to_path_buf(),
+            Compression::detect(path).unwrap_or(Compression::None),
+        )
+    })?;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn detect() {
+        let dir = std::env::temp_dir().join(format!("decompress-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // gzip data with a misleading extension
+        let gz = dir.join("data.csv.bz2");
+        let mut encoder =
+            flate2::write::GzEncoder::new(File::create(&gz).unwrap(), Default::default());
+        encoder.write_all(b"a,b\n1,2\n").unwrap();
+        encoder.finish().unwrap();
+        assert_eq!(Compression::detect(&gz).unwrap(), Compression::Gzip);
+        assert_eq!(read_to_string(&gz).unwrap(), "a,b\n1,2\n");
+
+        let csv = dir.join("plain.csv");
+        std::fs::write(&csv, "a,b\n").unwrap();
+        assert_eq!(Compression::detect(&csv).unwrap(), Compression::None);
+        assert_eq!(find(dir.join("plain.csv.z"), CSV_EXTENSIONS).unwrap(), csv);
+        assert!(matches!(
+            find(dir.join("missing.csv"), CSV_EXTENSIONS),
+            Err(DecompressError::NotFound(..))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -8,12 +8,24 @@ pub mod error;
 mod vector;
 pub use vector::Vector;
 mod monitors;
-pub use monitors::{Exertion, Mirror, Monitors, MonitorsError, MonitorsLoader};
+pub use monitors::export;
+#[cfg(feature = "windloading")]
+pub use monitors::windloading;
+pub use monitors::{
+    Components, Exertion, ExertionStats, FemNodes, Frame, FrameError, Frames, Gap, GroupError,
+    IntoSnapshots, MergePolicy, Mirror, MonitorEntry, MonitorGroup, MonitorGroups, MonitorKind,
+    Monitors, MonitorsCatalog, MonitorsError, MonitorsLoader, MonitorsStats, NodesError, PartNodes,
+    PartReport, PartSelection, Percentiles, Quantity, Series, SeriesStats, Snapshot, Snapshots,
+    Stats, Validation, ValidationReport, VectorStats,
+};
 pub mod cfd;
+pub mod config;
+pub mod decompress;
 pub mod domeseeing;
 pub use domeseeing::{Band, DomeSeeing};
 pub mod pressure;
 pub mod report;
+pub mod spectral;
 pub mod temperature;
 
 pub const FORCE_SAMPLING_FREQUENCY: f64 = 20_f64; // Hz
@@ -47,6 +59,21 @@ pub fn polyfit<T: na::RealField + Copy>(
         Err(error) => Err(error),
     }
 }
+/// Returns the median of the time steps of a time axis
+///
+/// The null and negative time steps, e.g. of duplicated or restarted time stamps, are ignored
+pub fn median_time_step(time: &[f64]) -> Option<f64> {
+    let mut dt: Vec<f64> = time
+        .windows(2)
+        .map(|t| t[1] - t[0])
+        .filter(|dt| *dt > 0f64)
+        .collect();
+    if dt.is_empty() {
+        return None;
+    }
+    dt.sort_by(f64::total_cmp);
+    Some(dt[dt.len() / 2])
+}
 pub fn detrend<T: na::RealField + Copy>(
     x_values: &[T],
     y_values: &[T],
@@ -270,6 +297,15 @@ mod tests {
         assert!((ba[0] - b).abs() < 1e-6 && (ba[1] - a).abs() < 1e-6)
     }
     #[test]
+    fn test_median_time_step() {
+        assert_eq!(
+            median_time_step(&[0., 0.5, 0.5, 1., 3., 2.5, 3.]),
+            Some(0.5)
+        );
+        assert_eq!(median_time_step(&[1., 1.]), None);
+        assert_eq!(median_time_step(&[]), None);
+    }
+    #[test]
     fn test_detrend() {
         let (a, b) = (-1.5f64, 5f64);
         let (x, y): (Vec<_>, Vec<_>) = (0..10).map(|k| (k as f64, a * k as f64 + b)).unzip();
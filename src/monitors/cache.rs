@@ -0,0 +1,386 @@
+//! On-disk cache of the parsed monitors
+//!
+//! The monitors are saved in a numpy npz archive next to the source file.
+//! The archive file name is derived from the header filters and the time range,
+//! and the archive includes a key made of the source file metadata (size and modification time)
+//! and of the filters, so a cache is discarded as soon as the source file is modified.
+//! The missing components of the forces, moments and vector quantities are saved as a validity mask
+//! next to the values, so NaN samples are restored as is.
+
+use super::{Exertion, Monitors, Quantity, Series};
+use crate::Vector;
+use npyz::{npz::NpzArchive, npz::NpzWriter, AutoSerialize, WriterBuilder};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+const VERSION: u32 = 3;
+
+/// Cache key of the monitors
+#[derive(Debug, PartialEq)]
+pub(crate) struct CacheKey {
+    version: u32,
+    source: PathBuf,
+    size: u64,
+    modified: u128,
+    header: String,
+    exclude: Option<String>,
+    quantities: Vec<(String, String)>,
+    time_range: (f64, f64),
+}
+impl CacheKey {
+    /// Creates the cache key of the monitors loaded from `source` with the given filters
+    pub fn new(
+        source: &Path,
+        header: &str,
+        exclude: Option<&str>,
+        quantities: &[(String, String)],
+        time_range: (f64, f64),
+    ) -> io::Result<Self> {
+        let metadata = source.metadata()?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        Ok(Self {
+            version: VERSION,
+            source: source.to_path_buf(),
+            size: metadata.len(),
+            modified,
+            header: header.to_owned(),
+            exclude: exclude.map(|x| x.to_owned()),
+            quantities: quantities.to_vec(),
+            time_range,
+        })
+    }
+    /// Path to the cache file
+    ///
+    /// A different cache file is used for each source file, set of filters and time range
+    pub fn path(&self) -> PathBuf {
+        let filters = format!(
+            "{:?}{:?}{:?}{:?}{:?}",
+            self.source, self.header, self.exclude, self.quantities, self.time_range
+        );
+        self.source
+            .with_file_name(format!("monitors.{:016x}.npz", fnv1a(filters.as_bytes())))
+    }
+    fn to_bytes(&self) -> Vec<u8> {
+        format!("{self:?}").into_bytes()
+    }
+}
+
+/// 64 bits FNV-1a hash, stable across builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Splits the optional values into the values, 0 if missing, and their validity mask
+fn to_array<I: IntoIterator<Item = Option<f64>>>(values: I) -> (Vec<f64>, Vec<u8>) {
+    values
+        .into_iter()
+        .map(|value| (value.unwrap_or_default(), value.is_some() as u8))
+        .unzip()
+}
+/// Merges the values and their validity mask into optional values
+fn from_array(values: Vec<f64>, mask: Vec<u8>) -> io::Result<Vec<Option<f64>>> {
+    if values.len() != mask.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "values and validity mask lengths mismatch",
+        ));
+    }
+    Ok(values
+        .into_iter()
+        .zip(mask)
+        .map(|(value, valid)| (valid != 0).then_some(value))
+        .collect())
+}
+
+/// Reads optional values and their validity mask, see [to_array], from the npz archive
+fn read_optional<R: io::Read + io::Seek>(
+    npz: &mut NpzArchive<R>,
+    name: &str,
+) -> io::Result<Vec<Option<f64>>> {
+    let missing = |name: &str| io::Error::new(io::ErrorKind::InvalidData, format!("no {name}"));
+    let values = npz
+        .by_name(name)?
+        .ok_or_else(|| missing(name))?
+        .into_vec()?;
+    let mask_name = format!("mask:{name}");
+    let mask = npz
+        .by_name(&mask_name)?
+        .ok_or_else(|| missing(&mask_name))?
+        .into_vec()?;
+    from_array(values, mask)
+}
+
+/// Loads the monitors from the cache
+///
+/// Returns `None` if there is no cache or if the cache does not match the key
+pub(crate) fn read(key: &CacheKey) -> Option<Monitors> {
+    match try_read(key) {
+        Ok(monitors) => monitors,
+        Err(e) => {
+            log::warn!("discarding monitors cache {:?}: {e}", key.path());
+            None
+        }
+    }
+}
+fn try_read(key: &CacheKey) -> io::Result<Option<Monitors>> {
+    let path = key.path();
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let mut npz = NpzArchive::open(&path)?;
+    let missing = |name: &str| io::Error::new(io::ErrorKind::InvalidData, format!("no {name}"));
+    let cached_key = npz
+        .by_name("key")?
+        .ok_or_else(|| missing("key"))?
+        .into_vec::<u8>()?;
+    if cached_key != key.to_bytes() {
+        return Ok(None);
+    }
+    let mut monitors = Monitors {
+        time: npz
+            .by_name("time")?
+            .ok_or_else(|| missing("time"))?
+            .into_vec()?,
+        ..Default::default()
+    };
+    let names: Vec<String> = npz.array_names().map(String::from).collect();
+    for name in names {
+        if let Some(key) = name.strip_prefix("htc:") {
+            let data = npz
+                .by_name(&name)?
+                .ok_or_else(|| missing(&name))?
+                .into_vec()?;
+            monitors
+                .heat_transfer_coefficients
+                .insert(key.to_owned(), data);
+        }
+        if let Some(key) = name.strip_prefix("fm:") {
+            let exertions = read_optional(&mut npz, &name)?
+                .chunks(6)
+                .map(|fm| Exertion {
+                    force: Vector {
+                        x: fm[0],
+                        y: fm[1],
+                        z: fm[2],
+                    },
+                    moment: Vector {
+                        x: fm[3],
+                        y: fm[4],
+                        z: fm[5],
+                    },
+                    cop: None,
+                })
+                .collect();
+            monitors
+                .forces_and_moments
+                .insert(key.to_owned(), exertions);
+        }
+        if let Some((quantity, monitor)) =
+            name.strip_prefix("q:").and_then(|key| key.rsplit_once(':'))
+        {
+            let npy = npz.by_name(&name)?.ok_or_else(|| missing(&name))?;
+            let series = if npy.shape().len() == 2 {
+                drop(npy);
+                Series::Vector(
+                    read_optional(&mut npz, &name)?
+                        .chunks(3)
+                        .map(|v| Vector {
+                            x: v[0],
+                            y: v[1],
+                            z: v[2],
+                        })
+                        .collect(),
+                )
+            } else {
+                Series::Scalar(npy.into_vec()?)
+            };
+            monitors
+                .quantities
+                .entry(quantity.to_owned())
+                .or_default()
+                .monitors
+                .insert(monitor.to_owned(), series);
+        }
+        if let Some(quantity) = name.strip_prefix("unit:") {
+            let unit = npz
+                .by_name(&name)?
+                .ok_or_else(|| missing(&name))?
+                .into_vec::<u8>()?;
+            monitors
+                .quantities
+                .entry(quantity.to_owned())
+                .or_default()
+                .unit = Some(String::from_utf8_lossy(&unit).into_owned());
+        }
+    }
+    log::info!("Monitors loaded from cache {:?}", path);
+    Ok(Some(monitors))
+}
+
+/// Writes an array into the npz archive
+fn write_array<W, T>(
+    npz: &mut NpzWriter<W>,
+    name: &str,
+    shape: &[u64],
+    data: &[T],
+) -> io::Result<()>
+where
+    W: io::Write + io::Seek,
+    T: AutoSerialize,
+{
+    let mut writer = npz
+        .array(name, Default::default())?
+        .default_dtype()
+        .shape(shape)
+        .begin_nd()?;
+    writer.extend(data)?;
+    writer.finish()
+}
+/// Writes optional values and their validity mask, see [to_array], into the npz archive
+fn write_optional<W, I>(
+    npz: &mut NpzWriter<W>,
+    name: &str,
+    shape: &[u64],
+    data: I,
+) -> io::Result<()>
+where
+    W: io::Write + io::Seek,
+    I: IntoIterator<Item = Option<f64>>,
+{
+    let (values, mask) = to_array(data);
+    write_array(npz, name, shape, &values)?;
+    write_array(npz, &format!("mask:{name}"), shape, &mask)
+}
+
+/// Saves the monitors to the cache
+///
+/// The cache is written to a temporary file that is then renamed, so an interrupted write
+/// never leaves a truncated cache behind
+pub(crate) fn write(key: &CacheKey, monitors: &Monitors) -> io::Result<()> {
+    let path = key.path();
+    let tmp = path.with_extension(format!("npz.{}.tmp", std::process::id()));
+    let written = write_npz(&tmp, key, monitors).and_then(|_| fs::rename(&tmp, &path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written?;
+    log::info!("Monitors cached to {:?}", path);
+    Ok(())
+}
+fn write_npz(path: &Path, key: &CacheKey, monitors: &Monitors) -> io::Result<()> {
+    let mut npz = NpzWriter::create(path)?;
+    let key = key.to_bytes();
+    write_array(&mut npz, "key", &[key.len() as u64], &key)?;
+    write_array(
+        &mut npz,
+        "time",
+        &[monitors.time.len() as u64],
+        &monitors.time,
+    )?;
+    for (key, data) in monitors.heat_transfer_coefficients.iter() {
+        write_array(&mut npz, &format!("htc:{key}"), &[data.len() as u64], data)?;
+    }
+    for (key, data) in monitors.forces_and_moments.iter() {
+        write_optional(
+            &mut npz,
+            &format!("fm:{key}"),
+            &[data.len() as u64, 6],
+            data.iter().flat_map(Exertion::components),
+        )?;
+    }
+    for (quantity, Quantity { unit, monitors }) in monitors.quantities.iter() {
+        if let Some(unit) = unit {
+            write_array(
+                &mut npz,
+                &format!("unit:{quantity}"),
+                &[unit.len() as u64],
+                unit.as_bytes(),
+            )?;
+        }
+        for (monitor, series) in monitors.iter() {
+            let name = format!("q:{quantity}:{monitor}");
+            match series {
+                Series::Scalar(data) => {
+                    write_array(&mut npz, &name, &[data.len() as u64], data)?;
+                }
+                Series::Vector(data) => {
+                    write_optional(
+                        &mut npz,
+                        &name,
+                        &[data.len() as u64, 3],
+                        data.iter().flat_map(|v| [v.x, v.y, v.z]),
+                    )?;
+                }
+            }
+        }
+    }
+    npz.zip_writer().finish()?.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let dir = std::env::temp_dir().join(format!("monitors-cache-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("monitors.csv.z");
+        std::fs::write(&source, b"data").unwrap();
+
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0.5, 1.0];
+        monitors
+            .heat_transfer_coefficients
+            .insert("M2".into(), vec![1., 2.]);
+        monitors.forces_and_moments.insert(
+            "M1cov1".into(),
+            vec![
+                Exertion::from_force_x(f64::NAN),
+                Exertion::from_moment_z(2.),
+            ],
+        );
+        let mut velocity = Quantity {
+            unit: Some("m/s".into()),
+            ..Default::default()
+        };
+        velocity.monitors.insert(
+            "probe1".into(),
+            Series::Vector(vec![Vector::from_x(3.), Vector::from_y(4.)]),
+        );
+        monitors.quantities.insert("Velocity".into(), velocity);
+
+        let key = CacheKey::new(&source, r"\w+", None, &[], (0., f64::INFINITY)).unwrap();
+        assert!(read(&key).is_none());
+        write(&key, &monitors).unwrap();
+        let cached = read(&key).unwrap();
+        assert_eq!(cached.time, monitors.time);
+        assert_eq!(cached.heat_transfer_coefficients["M2"], vec![1., 2.]);
+        let m1cov1 = &cached.forces_and_moments["M1cov1"];
+        assert!(m1cov1[0].force.x.is_some_and(f64::is_nan));
+        assert_eq!(m1cov1[0].force.y, None);
+        assert_eq!(m1cov1[1].moment.z, Some(2.));
+        assert_eq!(cached.quantities, monitors.quantities);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        let other = CacheKey::new(&source, "M1", None, &[], (0., f64::INFINITY)).unwrap();
+        assert_ne!(other.path(), key.path());
+        assert!(read(&other).is_none());
+
+        std::fs::write(&source, b"new data").unwrap();
+        let key = CacheKey::new(&source, r"\w+", None, &[], (0., f64::INFINITY)).unwrap();
+        assert!(read(&key).is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+//! Monitors catalog
+//!
+//! The catalog lists the monitors of a monitors file from the CSV header only,
+//! it is returned by [MonitorsLoader::catalog](super::MonitorsLoader::catalog).
+
+use std::{fmt, ops::Deref};
+
+/// Type of monitored quantity
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MonitorKind {
+    Force,
+    Moment,
+    HeatTransferCoefficient,
+    /// Any other quantity, see [Quantity](super::Quantity)
+    Other(String),
+}
+impl fmt::Display for MonitorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonitorKind::Force => write!(f, "Force"),
+            MonitorKind::Moment => write!(f, "Moment"),
+            MonitorKind::HeatTransferCoefficient => write!(f, "HTC"),
+            MonitorKind::Other(quantity) => write!(f, "{quantity}"),
+        }
+    }
+}
+
+/// Vector components present in the monitors file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Components {
+    pub x: bool,
+    pub y: bool,
+    pub z: bool,
+}
+impl Components {
+    /// Returns true for a scalar monitor
+    pub fn is_scalar(&self) -> bool {
+        !(self.x || self.y || self.z)
+    }
+}
+impl fmt::Display for Components {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_scalar() {
+            return write!(f, "-");
+        }
+        for (present, axis) in [(self.x, 'X'), (self.y, 'Y'), (self.z, 'Z')] {
+            if present {
+                write!(f, "{axis}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Monitor of a part
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorEntry {
+    /// Part name, i.e. the key of the monitor in [Monitors](super::Monitors)
+    pub part: String,
+    pub kind: MonitorKind,
+    pub unit: Option<String>,
+    pub components: Components,
+}
+
+/// List of the monitors in a monitors file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MonitorsCatalog(pub(crate) Vec<MonitorEntry>);
+impl Deref for MonitorsCatalog {
+    type Target = Vec<MonitorEntry>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl MonitorsCatalog {
+    /// Returns the names of the monitored parts
+    pub fn parts(&self) -> Vec<&str> {
+        let mut parts: Vec<&str> = self.0.iter().map(|e| e.part.as_str()).collect();
+        parts.sort_unstable();
+        parts.dedup();
+        parts
+    }
+    /// Returns the monitors of the given kind
+    pub fn kind(&self, kind: MonitorKind) -> impl Iterator<Item = &MonitorEntry> {
+        self.0.iter().filter(move |e| e.kind == kind)
+    }
+}
+impl fmt::Display for MonitorsCatalog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|e| e.part.len())
+            .max()
+            .unwrap_or(4)
+            .max(4);
+        writeln!(
+            f,
+            "{:<width$} {:<10} {:<10} Quantity",
+            "Part", "Components", "Unit"
+        )?;
+        for e in &self.0 {
+            writeln!(
+                f,
+                "{:<width$} {:<10} {:<10} {}",
+                e.part,
+                e.components.to_string(),
+                e.unit.as_deref().unwrap_or("-"),
+                e.kind
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+//! Monitors and mirror loads export
+//!
+//! [Monitors] and [Mirror] are flattened into a [MonitorsTable] of time series columns
+//! that is written to Parquet (`polars` feature), npz or MATLAB `.mat` (`matio-rs` feature) files.
+//!
+//! The columns are named after the element and the component, e.g. `M1cell_Fx`, `M1cell_My` or `S3_Fz`
+//! for forces and moments, `{element}_HTC` for heat transfer coefficients and `{monitor}_{quantity}` or
+//! `{monitor}_{quantity}_{x|y|z}` for the other quantities.
+//! Any character that is not alphanumeric is replaced by an underscore, so the names are also valid MATLAB identifiers.
+//! The time vector is the first column, named `Time`.
+
+use super::{Exertion, Mirror, Monitors, Series};
+use npyz::{npz::NpzWriter, WriterBuilder};
+use std::{io, path::Path};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExportError {
+    #[error("column {0} has {1} samples instead of {2}")]
+    Length(String, usize, usize),
+    #[error("failed to write the monitors")]
+    Io(#[from] io::Error),
+    #[error("failed to write the monitors to CSV")]
+    Csv(#[from] csv::Error),
+    #[cfg(feature = "polars")]
+    #[error("failed to write the monitors to Parquet")]
+    Polars(#[from] polars::prelude::PolarsError),
+    #[cfg(feature = "matio-rs")]
+    #[error("failed to write the monitors to MATLAB: {0}")]
+    Matio(String),
+}
+type Result<T> = std::result::Result<T, ExportError>;
+
+/// Returns the column name of the given element and component
+pub fn column_name(element: &str, component: &str) -> String {
+    format!("{element}_{component}")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Time series columns
+#[derive(Debug, Clone, Default)]
+pub struct MonitorsTable {
+    /// Time [s]
+    pub time: Vec<f64>,
+    /// Column names and values, missing values are NaN
+    pub columns: Vec<(String, Vec<f64>)>,
+}
+impl MonitorsTable {
+    fn push(&mut self, name: String, values: Vec<f64>) -> Result<()> {
+        if values.len() != self.time.len() {
+            return Err(ExportError::Length(name, values.len(), self.time.len()));
+        }
+        self.columns.push((name, values));
+        Ok(())
+    }
+    fn push_exertion<'a, I>(&mut self, element: &str, values: I) -> Result<()>
+    where
+        I: IntoIterator<Item = &'a Exertion>,
+    {
+        let values: Vec<[f64; 6]> = values
+            .into_iter()
+            .map(|e| e.components().map(|x| x.unwrap_or(f64::NAN)))
+            .collect();
+        for (i, component) in Exertion::COMPONENTS.iter().enumerate() {
+            self.push(
+                column_name(element, component),
+                values.iter().map(|v| v[i]).collect(),
+            )?;
+        }
+        Ok(())
+    }
+    /// Returns the column names, starting with `Time`
+    pub fn names(&self) -> Vec<&str> {
+        std::iter::once("Time")
+            .chain(self.columns.iter().map(|(name, _)| name.as_str()))
+            .collect()
+    }
+    /// Writes the table to a CSV file
+    pub fn to_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut wtr = csv::Writer::from_path(path)?;
+        wtr.write_record(self.names())?;
+        for (k, t) in self.time.iter().enumerate() {
+            let mut record = vec![t.to_string()];
+            record.extend(self.columns.iter().map(|(_, values)| values[k].to_string()));
+            wtr.write_record(&record)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+    /// Writes the table to a numpy npz file, with one array per column
+    pub fn to_npz<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut npz = NpzWriter::create(path)?;
+        for (name, values) in std::iter::once(("Time", &self.time))
+            .chain(self.columns.iter().map(|(n, v)| (n.as_str(), v)))
+        {
+            let mut writer = npz
+                .array(name, Default::default())?
+                .default_dtype()
+                .shape(&[values.len() as u64])
+                .begin_nd()?;
+            writer.extend(values.iter().cloned())?;
+            writer.finish()?;
+        }
+        Ok(())
+    }
+    /// Returns the table as a polars data frame
+    #[cfg(feature = "polars")]
+    pub fn to_dataframe(&self) -> Result<polars::prelude::DataFrame> {
+        use polars::prelude::*;
+        let columns: Vec<Column> = std::iter::once(("Time", &self.time))
+            .chain(self.columns.iter().map(|(n, v)| (n.as_str(), v)))
+            .map(|(name, values)| Column::new(name.into(), values))
+            .collect();
+        Ok(DataFrame::new(columns)?)
+    }
+    /// Writes the table to a Parquet file
+    #[cfg(feature = "polars")]
+    pub fn to_parquet<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        use polars::prelude::ParquetWriter;
+        let mut df = self.to_dataframe()?;
+        ParquetWriter::new(std::fs::File::create(path)?).finish(&mut df)?;
+        Ok(())
+    }
+    /// Writes the table to a MATLAB file as the structure `name` with one field per column
+    #[cfg(feature = "matio-rs")]
+    pub fn to_mat<P: AsRef<Path>>(&self, path: P, name: &str) -> Result<()> {
+        use matio_rs::{Field, MatFile, MatStruct, Save};
+        fn matio<E: std::fmt::Display>(e: E) -> ExportError {
+            ExportError::Matio(e.to_string())
+        }
+        let mut mat = MatStruct::new(name)
+            .field("Time", &self.time)
+            .map_err(matio)?;
+        for (name, values) in self.columns.iter() {
+            mat = mat.field(name, values).map_err(matio)?;
+        }
+        let mat_file = MatFile::save(path.as_ref()).map_err(matio)?;
+        mat_file.write(mat.build().map_err(matio)?);
+        Ok(())
+    }
+}
+
+impl TryFrom<&Monitors> for MonitorsTable {
+    type Error = ExportError;
+
+    fn try_from(monitors: &Monitors) -> Result<Self> {
+        let mut table = MonitorsTable {
+            time: monitors.time.clone(),
+            ..Default::default()
+        };
+        for (key, values) in monitors.heat_transfer_coefficients.iter() {
+            table.push(column_name(key, "HTC"), values.clone())?;
+        }
+        for (key, values) in monitors.forces_and_moments.iter() {
+            table.push_exertion(key, values)?;
+        }
+        for (quantity, q) in monitors.quantities.iter() {
+            for (monitor, series) in q.monitors.iter() {
+                let name = column_name(monitor, quantity);
+                match series {
+                    Series::Scalar(values) => table.push(name, values.clone())?,
+                    Series::Vector(values) => {
+                        let values: Vec<[f64; 3]> = values
+                            .iter()
+                            .map(|v| [v.x, v.y, v.z].map(|x| x.unwrap_or(f64::NAN)))
+                            .collect();
+                        for (i, axis) in ["x", "y", "z"].iter().enumerate() {
+                            table.push(
+                                column_name(&name, axis),
+                                values.iter().map(|v| v[i]).collect(),
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(table)
+    }
+}
+impl TryFrom<&Mirror> for MonitorsTable {
+    type Error = ExportError;
+
+    fn try_from(mirror: &Mirror) -> Result<Self> {
+        let mut table = MonitorsTable {
+            time: mirror.time().iter().cloned().collect(),
+            ..Default::default()
+        };
+        for (key, values) in mirror.forces_and_moments().iter() {
+            table.push_exertion(key, values)?;
+        }
+        Ok(table)
+    }
+}
+
+impl Monitors {
+    /// Returns the monitors as a [MonitorsTable]
+    pub fn to_table(&self) -> Result<MonitorsTable> {
+        self.try_into()
+    }
+    /// Writes the monitors to a numpy npz file, see [MonitorsTable::to_npz]
+    pub fn to_npz<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.to_table()?.to_npz(path)
+    }
+    /// Writes the monitors to a Parquet file, see [MonitorsTable::to_parquet]
+    #[cfg(feature = "polars")]
+    pub fn to_parquet<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.to_table()?.to_parquet(path)
+    }
+    /// Writes the monitors to a MATLAB file in the structure `monitors`, see [MonitorsTable::to_mat]
+    #[cfg(feature = "matio-rs")]
+    pub fn to_mat<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.to_table()?.to_mat(path, "monitors")
+    }
+}
+impl Mirror {
+    /// Returns the segments forces and moments as a [MonitorsTable]
+    pub fn to_table(&self) -> Result<MonitorsTable> {
+        self.try_into()
+    }
+    /// Writes the segments forces and moments to a numpy npz file, see [MonitorsTable::to_npz]
+    pub fn to_npz<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.to_table()?.to_npz(path)
+    }
+    /// Writes the segments forces and moments to a Parquet file, see [MonitorsTable::to_parquet]
+    #[cfg(feature = "polars")]
+    pub fn to_parquet<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.to_table()?.to_parquet(path)
+    }
+    /// Writes the segments forces and moments to a MATLAB file in the structure `m1` or `m2`, see [MonitorsTable::to_mat]
+    #[cfg(feature = "matio-rs")]
+    pub fn to_mat<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let name = match self {
+            Mirror::M1 { .. } => "m1",
+            Mirror::M2 { .. } => "m2",
+        };
+        self.to_table()?.to_mat(path, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use npyz::npz::NpzArchive;
+
+    #[test]
+    fn npz() {
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0., 0.05, 0.1];
+        monitors
+            .heat_transfer_coefficients
+            .insert("M1-cell".into(), vec![1., 2., 3.]);
+        monitors.forces_and_moments.insert(
+            "M1cell".into(),
+            (0..3)
+                .map(|i| Exertion {
+                    force: [i as f64, 0., 1.].into(),
+                    moment: crate::Vector::from_x(-1.),
+                    cop: None,
+                })
+                .collect(),
+        );
+        let table = monitors.to_table().unwrap();
+        assert_eq!(
+            table.names(),
+            vec![
+                "Time",
+                "M1_cell_HTC",
+                "M1cell_Fx",
+                "M1cell_Fy",
+                "M1cell_Fz",
+                "M1cell_Mx",
+                "M1cell_My",
+                "M1cell_Mz"
+            ]
+        );
+        let dir = std::env::temp_dir().join(format!("monitors-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("monitors.npz");
+        monitors.to_npz(&path).unwrap();
+        let mut npz = NpzArchive::open(&path).unwrap();
+        let time: Vec<f64> = npz.by_name("Time").unwrap().unwrap().into_vec().unwrap();
+        assert_eq!(time, monitors.time);
+        let fx: Vec<f64> = npz
+            .by_name("M1cell_Fx")
+            .unwrap()
+            .unwrap()
+            .into_vec()
+            .unwrap();
+        assert_eq!(fx, vec![0., 1., 2.]);
+        let my: Vec<f64> = npz
+            .by_name("M1cell_My")
+            .unwrap()
+            .unwrap()
+            .into_vec()
+            .unwrap();
+        assert!(my.iter().all(|x| x.is_nan()));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,374 @@
+//! Coordinate frames
+//!
+//! The CFD forces are given in the OSS and the moments are taken about the OSS origin.
+//! A [Frame] is defined by its origin and by its axes in the OSS,
+//! transforming an [Exertion] into a frame rotates the force and transfers the moment to the frame origin.
+//!
+//! Frames are either built from:
+//!  - the mount azimuth/elevation angles: [Frame::mount],
+//!  - the M1 and M2 segments local coordinate systems: [Frame::m1_segment] and [Frame::m2_segment],
+//!  - any user-defined origin and axes: [Frame::new],
+//!
+//! or loaded from a TOML or JSON file with [Frames::from_path], e.g.
+//! ```toml
+//! [M1cov1]
+//! origin = [0.0, 13.562, 5.3064]
+//!
+//! [M2s3]
+//! m2_segment = 3
+//!
+//! [Cring_strut_1]
+//! origin = [3.0, 0.0, 1.0]
+//! axes = [[0.0, 1.0, 0.0], [-1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]
+//!
+//! [mount]
+//! zenith = 30.0
+//! azimuth = 45.0
+//! ```
+//! Unknown keys are rejected and the user-defined axes must be orthonormal.
+
+use super::{Exertion, Monitors};
+use crate::{
+    cfd::{Azimuth, ZenithAngle},
+    config, Vector,
+};
+use geotrans::{Segment, SegmentTrait, Transform, M1, M2};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum FrameError {
+    #[error("failed to load the frames file")]
+    Config(#[from] crate::config::ConfigError),
+    #[error("invalid segment frame")]
+    Geotrans(#[from] geotrans::Error),
+    #[error("invalid mount frame")]
+    Mount(#[from] crate::cfd::CfdError),
+    #[error("frame axes {0:?} are not orthonormal")]
+    Axes([[f64; 3]; 3]),
+}
+type Result<T> = std::result::Result<T, FrameError>;
+
+const IDENTITY: [[f64; 3]; 3] = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+fn dot(u: &[f64; 3], v: &[f64; 3]) -> f64 {
+    u.iter().zip(v).map(|(u, v)| u * v).sum()
+}
+fn cross(u: &[f64; 3], v: &[f64; 3]) -> [f64; 3] {
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+/// Coordinate frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Frame {
+    /// Frame origin in the OSS [m]
+    #[serde(default)]
+    pub origin: [f64; 3],
+    /// Frame x, y and z unit axes in the OSS
+    #[serde(default = "identity")]
+    pub axes: [[f64; 3]; 3],
+}
+fn identity() -> [[f64; 3]; 3] {
+    IDENTITY
+}
+impl Default for Frame {
+    /// The OSS
+    fn default() -> Self {
+        Self {
+            origin: [0.; 3],
+            axes: IDENTITY,
+        }
+    }
+}
+impl Frame {
+    /// Creates a frame from its origin and its axes in the OSS
+    pub fn new(origin: [f64; 3], axes: [[f64; 3]; 3]) -> Self {
+        Self { origin, axes }
+    }
+    /// The OSS
+    pub fn oss() -> Self {
+        Default::default()
+    }
+    /// Creates a frame with the OSS axes and the given origin
+    pub fn at(origin: [f64; 3]) -> Self {
+        Self {
+            origin,
+            ..Default::default()
+        }
+    }
+    /// Sets the frame origin in the OSS
+    pub fn origin(self, origin: [f64; 3]) -> Self {
+        Self { origin, ..self }
+    }
+    /// Mount frame
+    ///
+    /// The mount frame z axis is vertical, it is derived from the OSS by a rotation of the zenith angle
+    /// around the elevation axis (OSS x axis) followed by a rotation of the azimuth angle around the vertical axis.
+    /// The origin is the OSS origin.
+    pub fn mount(zenith: ZenithAngle, azimuth: Azimuth) -> Self {
+        let (sz, cz) = f64::from(zenith).to_radians().sin_cos();
+        let (sa, ca) = f64::from(azimuth).to_radians().sin_cos();
+        // rotation from the OSS to the mount: Rz(azimuth) * Rx(zenith)
+        Self::new(
+            [0.; 3],
+            [
+                [ca, -sa * cz, sa * sz],
+                [sa, ca * cz, -ca * sz],
+                [0., sz, cz],
+            ],
+        )
+    }
+    fn segment<M>(sid: usize) -> Result<Self>
+    where
+        Segment<M>: SegmentTrait,
+    {
+        let segment = || Segment::<M>::new(sid as i32);
+        let origin = [0f64; 3].to(segment())?;
+        let mut axes = IDENTITY;
+        for axis in axes.iter_mut() {
+            *axis = axis.vtov(segment())?;
+        }
+        Ok(Self { origin, axes })
+    }
+    /// M1 segment `sid` (1 to 7) local coordinate system
+    pub fn m1_segment(sid: usize) -> Result<Self> {
+        Self::segment::<M1>(sid)
+    }
+    /// M2 segment `sid` (1 to 7) local coordinate system
+    pub fn m2_segment(sid: usize) -> Result<Self> {
+        Self::segment::<M2>(sid)
+    }
+    /// Checks that the frame axes are unit vectors orthogonal to each other
+    pub fn is_orthonormal(&self) -> bool {
+        (0..3).all(|i| {
+            (0..3).all(|j| {
+                let delta = if i == j { 1. } else { 0. };
+                (dot(&self.axes[i], &self.axes[j]) - delta).abs() < 1e-6
+            })
+        })
+    }
+    fn rotate(&self, v: &[f64; 3]) -> [f64; 3] {
+        self.axes.map(|axis| dot(&axis, v))
+    }
+    fn rotate_back(&self, v: &[f64; 3]) -> [f64; 3] {
+        let [x, y, z] = self.axes;
+        [0, 1, 2].map(|i| x[i] * v[0] + y[i] * v[1] + z[i] * v[2])
+    }
+    /// Transforms a point given in the OSS into the frame
+    pub fn point_from_oss(&self, p: &[f64; 3]) -> [f64; 3] {
+        self.rotate(&[0, 1, 2].map(|i| p[i] - self.origin[i]))
+    }
+    /// Transforms a point given in the frame into the OSS
+    pub fn point_to_oss(&self, p: &[f64; 3]) -> [f64; 3] {
+        let q = self.rotate_back(p);
+        [0, 1, 2].map(|i| q[i] + self.origin[i])
+    }
+}
+
+/// Applies `f` to the components of the force and of the moment, missing components are left untouched
+fn map_exertion<F>(e: &mut Exertion, f: F)
+where
+    F: Fn([f64; 3], [f64; 3]) -> ([f64; 3], [f64; 3]),
+{
+    if let (Some(force), Some(moment)) = (
+        Option::<[f64; 3]>::from(&e.force),
+        Option::<[f64; 3]>::from(&e.moment),
+    ) {
+        let (force, moment) = f(force, moment);
+        e.force = force.into();
+        e.moment = moment.into();
+    }
+}
+
+impl Exertion {
+    /// Transforms the force and the moment given in the OSS, with the moment about the OSS origin,
+    /// into the frame, with the moment about the frame origin
+    ///
+    /// Exertions with missing force or moment components are left untouched
+    pub fn into_frame(&mut self, frame: &Frame) -> &mut Self {
+        map_exertion(self, |force, moment| {
+            let arm = cross(&frame.origin, &force);
+            let moment = [0, 1, 2].map(|i| moment[i] - arm[i]);
+            (frame.rotate(&force), frame.rotate(&moment))
+        });
+        if let Some(cop) = self.cop.as_ref().and_then(Option::<[f64; 3]>::from) {
+            self.cop = Some(Vector::from(frame.point_from_oss(&cop)));
+        }
+        self
+    }
+    /// Transforms the force and the moment given in the frame back into the OSS
+    pub fn from_frame(&mut self, frame: &Frame) -> &mut Self {
+        map_exertion(self, |force, moment| {
+            let force = frame.rotate_back(&force);
+            let moment = frame.rotate_back(&moment);
+            let arm = cross(&frame.origin, &force);
+            (force, [0, 1, 2].map(|i| moment[i] + arm[i]))
+        });
+        if let Some(cop) = self.cop.as_ref().and_then(Option::<[f64; 3]>::from) {
+            self.cop = Some(Vector::from(frame.point_to_oss(&cop)));
+        }
+        self
+    }
+}
+
+/// Frames keyed by monitor name
+#[derive(Debug, Clone, Default)]
+pub struct Frames(BTreeMap<String, Frame>);
+impl Deref for Frames {
+    type Target = BTreeMap<String, Frame>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl DerefMut for Frames {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl FromIterator<(String, Frame)> for Frames {
+    fn from_iter<T: IntoIterator<Item = (String, Frame)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Frame definition in a frames file
+#[derive(Debug, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum FrameDefinition {
+    M1Segment { m1_segment: usize },
+    M2Segment { m2_segment: usize },
+    Mount { zenith: f64, azimuth: f64 },
+    Frame(Frame),
+}
+impl TryFrom<FrameDefinition> for Frame {
+    type Error = FrameError;
+
+    fn try_from(definition: FrameDefinition) -> Result<Self> {
+        match definition {
+            FrameDefinition::M1Segment { m1_segment } => Frame::m1_segment(m1_segment),
+            FrameDefinition::M2Segment { m2_segment } => Frame::m2_segment(m2_segment),
+            FrameDefinition::Mount { zenith, azimuth } => Ok(Frame::mount(
+                ZenithAngle::custom(zenith)?,
+                Azimuth::custom(azimuth)?,
+            )),
+            FrameDefinition::Frame(frame) if frame.is_orthonormal() => Ok(frame),
+            FrameDefinition::Frame(frame) => Err(FrameError::Axes(frame.axes)),
+        }
+    }
+}
+
+impl Frames {
+    /// Loads the frames from a TOML or a JSON file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let definitions: BTreeMap<String, FrameDefinition> = config::from_path(path)?;
+        definitions
+            .into_iter()
+            .map(|(key, definition)| Ok((key, definition.try_into()?)))
+            .collect()
+    }
+}
+
+impl Monitors {
+    /// Transforms the forces and moments of all the monitors into the frame
+    pub fn into_frame(&mut self, frame: &Frame) -> &mut Self {
+        self.forces_and_moments
+            .values_mut()
+            .flat_map(|value| value.iter_mut())
+            .for_each(|e| {
+                e.into_frame(frame);
+            });
+        self.total_forces_and_moments.clear();
+        self
+    }
+    /// Transforms the forces and moments of each monitor into its frame
+    ///
+    /// The monitors without a frame are left in the OSS
+    pub fn into_frames(&mut self, frames: &Frames) -> &mut Self {
+        for (key, value) in self.forces_and_moments.iter_mut() {
+            match frames.get(key) {
+                Some(frame) => value.iter_mut().for_each(|e| {
+                    e.into_frame(frame);
+                }),
+                None => log::warn!("no frame for monitor {key}, it is left in the OSS"),
+            }
+        }
+        self.total_forces_and_moments.clear();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        assert!(
+            a.iter().zip(&b).all(|(a, b)| (a - b).abs() < 1e-9),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let exertion = Exertion::from(([1., 2., 3.], ([10., 0., -5.], [1., -2., 4.])));
+        let frame = Frame::new([1., 2., 3.], [[0., 1., 0.], [-1., 0., 0.], [0., 0., 1.]]);
+        let mut e = exertion.clone();
+        e.into_frame(&frame);
+        assert_close(Option::from(&e.force).unwrap(), [0., -10., -5.]);
+        // moment about the frame origin: M - o x F = [1,-2,4] - [-10,35,-20] = [11,-37,24]
+        assert_close(Option::from(&e.moment).unwrap(), [-37., -11., 24.]);
+        assert_close(Option::from(e.cop.as_ref().unwrap()).unwrap(), [0.; 3]);
+        e.from_frame(&frame);
+        assert_close(Option::from(&e.force).unwrap(), [10., 0., -5.]);
+        assert_close(Option::from(&e.moment).unwrap(), [1., -2., 4.]);
+
+        // the mount z axis is vertical
+        let mount = Frame::mount(ZenithAngle::Thirty, Azimuth::Ninety);
+        let (s, c) = 30f64.to_radians().sin_cos();
+        assert_close(mount.rotate_back(&[0., 0., 1.]), [0., s, c]);
+        let mut e = Exertion::from_force(Vector::from([0., 0., 1.]));
+        e.moment = Vector::zero();
+        e.into_frame(&Frame::mount(ZenithAngle::Thirty, Azimuth::Zero));
+        assert_close(Option::from(&e.force).unwrap(), [0., -s, c]);
+
+        let m1s1 = Frame::m1_segment(1).unwrap();
+        assert_close(m1s1.point_from_oss(&m1s1.origin), [0.; 3]);
+        assert!(Frame::m1_segment(8).is_err());
+
+        let frames: BTreeMap<String, FrameDefinition> = toml::from_str(
+            r#"
+[M1cov1]
+origin = [0.0, 13.562, 5.3064]
+[M2s3]
+m2_segment = 3
+[mount]
+zenith = 30.0
+azimuth = 90.0
+"#,
+        )
+        .unwrap();
+        let frames: Frames = frames
+            .into_iter()
+            .map(|(k, v)| (k, Frame::try_from(v).unwrap()))
+            .collect();
+        assert_eq!(frames["M1cov1"], Frame::at([0.0, 13.562, 5.3064]));
+        assert_eq!(frames["M2s3"], Frame::m2_segment(3).unwrap());
+        assert_eq!(frames["mount"], mount);
+
+        assert!(toml::from_str::<FrameDefinition>("orign = [1.0, 0.0, 0.0]").is_err());
+        assert!(toml::from_str::<FrameDefinition>("m1_segment = 1\nzenith = 30.0").is_err());
+        let skewed: FrameDefinition =
+            toml::from_str("axes = [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 0.0, 1.0]]").unwrap();
+        assert!(matches!(Frame::try_from(skewed), Err(FrameError::Axes(_))));
+    }
+}
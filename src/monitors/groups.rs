@@ -0,0 +1,332 @@
+//! Monitor groups
+//!
+//! A [MonitorGroup] is a named selection of monitored parts, see [PartSelection],
+//! and [MonitorGroups] is an ordered list of groups.
+//! [Monitors::groups] sums the forces and moments of the parts of each group into a new [Monitors]
+//! with one entry per group, the moments are transferred to the group reference point if any.
+//!
+//! The groups are loaded with [MonitorGroups::from_path] from a TOML or a JSON file, e.g.
+//! ```toml
+//! [[group]]
+//! name = "C-Rings"
+//! parts = { include = "Cring", exclude = "M1level" }
+//!
+//! [[group]]
+//! name = "GIR"
+//! parts = ["GIR"]
+//! reference = [0.0, 0.0, -3.0]
+//! ```
+
+use super::{Exertion, Monitors, PartSelection};
+use crate::{config, Vector};
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GroupError {
+    #[error("failed to load the groups file")]
+    Config(#[from] crate::config::ConfigError),
+    #[error("invalid group selection regular expression")]
+    Regex(#[from] regex::Error),
+    #[error("group {0} not found")]
+    MissingGroup(String),
+}
+type Result<T> = std::result::Result<T, GroupError>;
+
+/// Named selection of monitored parts
+#[derive(Debug, Clone, Deserialize)]
+pub struct MonitorGroup {
+    pub name: String,
+    pub parts: PartSelection,
+    /// Reference point in the OSS of the group moments, the OSS origin if `None`
+    #[serde(default)]
+    pub reference: Option<[f64; 3]>,
+}
+impl MonitorGroup {
+    pub fn new<S: Into<String>>(name: S, parts: PartSelection) -> Self {
+        Self {
+            name: name.into(),
+            parts,
+            reference: None,
+        }
+    }
+    /// Group of the parts matching `include`
+    pub fn matching<S: Into<String>, T: Into<String>>(name: S, include: T) -> Self {
+        Self::new(
+            name,
+            PartSelection::Matching {
+                include: include.into(),
+                exclude: None,
+            },
+        )
+    }
+    /// Excludes the parts matching `exclude` from a [PartSelection::Matching] group
+    pub fn exclude<S: Into<String>>(self, exclude: S) -> Self {
+        match self.parts {
+            PartSelection::Matching { include, .. } => Self {
+                parts: PartSelection::Matching {
+                    include,
+                    exclude: Some(exclude.into()),
+                },
+                ..self
+            },
+            PartSelection::List(_) => self,
+        }
+    }
+    /// Sets the reference point of the group moments
+    pub fn reference(self, reference: [f64; 3]) -> Self {
+        Self {
+            reference: Some(reference),
+            ..self
+        }
+    }
+    /// Returns the names of the group parts among the monitors
+    pub fn select(&self, monitors: &Monitors) -> Result<Vec<String>> {
+        Ok(self.parts.select(monitors.forces_and_moments.keys())?)
+    }
+    /// Returns the monitors of the group parts
+    pub fn subset(&self, monitors: &Monitors) -> Result<Monitors> {
+        let parts = self.select(monitors)?;
+        let subset = Monitors {
+            time: monitors.time.clone(),
+            forces_and_moments: parts
+                .iter()
+                .map(|part| (part.clone(), monitors.forces_and_moments[part].clone()))
+                .collect(),
+            heat_transfer_coefficients: monitors
+                .heat_transfer_coefficients
+                .iter()
+                .filter(|(key, _)| parts.contains(key))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            ..Default::default()
+        };
+        Ok(subset)
+    }
+    /// Returns the sum of the forces and moments of the group parts
+    ///
+    /// Returns `None` if none of the monitors belongs to the group
+    pub fn exertion(&self, monitors: &Monitors) -> Result<Option<Vec<Exertion>>> {
+        let parts = self.select(monitors)?;
+        if parts.is_empty() {
+            return Ok(None);
+        }
+        let n = monitors.len();
+        let (force, moment) = parts
+            .iter()
+            .map(|part| &monitors.forces_and_moments[part])
+            .fold(
+                (vec![Vector::zero(); n], vec![Vector::zero(); n]),
+                |(mut fa, mut ma), value| {
+                    fa.iter_mut()
+                        .zip(value.iter())
+                        .for_each(|(mut fa, e)| fa += &e.force);
+                    ma.iter_mut()
+                        .zip(value.iter())
+                        .for_each(|(mut ma, e)| ma += &e.moment);
+                    (fa, ma)
+                },
+            );
+        Ok(Some(
+            force
+                .into_iter()
+                .zip(moment)
+                .map(|(force, moment)| {
+                    let mut e = Exertion {
+                        force,
+                        moment,
+                        cop: None,
+                    };
+                    if let Some(reference) = self.reference {
+                        e.into_local(reference.into());
+                    }
+                    e
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct GroupsFile {
+    group: Vec<MonitorGroup>,
+}
+
+/// Ordered list of [MonitorGroup]s
+#[derive(Debug, Clone, Default)]
+pub struct MonitorGroups(Vec<MonitorGroup>);
+impl MonitorGroups {
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds a group
+    pub fn group(mut self, group: MonitorGroup) -> Self {
+        self.0.push(group);
+        self
+    }
+    /// Loads the groups from a TOML or a JSON file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let GroupsFile { group } = config::from_path(path)?;
+        Ok(Self(group))
+    }
+    /// Mount structure groups
+    ///
+    /// C-Rings, GIR, LGS, M1 assembly, M1 covers, M2 & Top-End, Trusses and Platform & Trays,
+    /// each group excludes the parts of the other groups and the M1 level floor
+    pub fn mount() -> Self {
+        let groups = [
+            ("C-Rings", "Cring"),
+            ("GIR", "GIR"),
+            ("LGS", "LGS"),
+            ("M1 assembly", "M1"),
+            ("M1 covers", "M1cov"),
+            ("M2 & Top-End", "M2|Topend"),
+            ("Trusses", "Tbot|Tup|arm|cabletruss"),
+            ("Platform & Trays", "cabletrays|platform|Cabs"),
+        ];
+        groups
+            .iter()
+            .map(|&(name, include)| {
+                let exclude: Vec<&str> = groups
+                    .iter()
+                    .map(|&(_, x)| x)
+                    .filter(|&x| x != include && !(include == "M1cov" && x == "M1"))
+                    .chain(Some("M1level"))
+                    .collect();
+                MonitorGroup::matching(name, include).exclude(exclude.join("|"))
+            })
+            .collect()
+    }
+    /// Telescope parts groups
+    ///
+    /// C-Rings, M1 cell, upper and lower trusses, top-end, M2 segments, M1 and M2 baffles,
+    /// M1 outer and inner covers, GIR, PFA arms, LGS and platforms & cables
+    pub fn parts() -> Self {
+        [
+            ("c-ring_parts", "Cring"),
+            ("m1-cell", "M1cell"),
+            ("upper-truss", "Tu"),
+            ("lower-truss", "Tb"),
+            ("top-end", "Top"),
+            ("m2-segments", "M2s"),
+            ("m12-baffles", "Baf"),
+            ("m1-outer-covers", "M1cov[1-6]"),
+            ("m1-inner-covers", "M1covin[1-6]"),
+            ("gir", "GIR"),
+            ("pfa-arms", "arm"),
+            ("lgs", "LGS"),
+            ("platforms-cables", "cable|plat|level"),
+        ]
+        .into_iter()
+        .map(|(name, include)| MonitorGroup::matching(name, include))
+        .collect()
+    }
+    /// Returns the group `name`
+    pub fn get(&self, name: &str) -> Result<&MonitorGroup> {
+        self.0
+            .iter()
+            .find(|group| group.name == name)
+            .ok_or_else(|| GroupError::MissingGroup(name.to_string()))
+    }
+    /// Returns the group names, in order
+    pub fn names(&self) -> Vec<&str> {
+        self.0.iter().map(|group| group.name.as_str()).collect()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &MonitorGroup> {
+        self.0.iter()
+    }
+}
+impl FromIterator<MonitorGroup> for MonitorGroups {
+    fn from_iter<T: IntoIterator<Item = MonitorGroup>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+impl<'a> IntoIterator for &'a MonitorGroups {
+    type Item = &'a MonitorGroup;
+    type IntoIter = std::slice::Iter<'a, MonitorGroup>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Monitors {
+    /// Returns the monitors of the summed forces and moments of each group
+    ///
+    /// The groups without any monitor are skipped with a warning
+    pub fn groups(&self, groups: &MonitorGroups) -> Result<Monitors> {
+        let mut monitors = Monitors {
+            time: self.time.clone(),
+            ..Default::default()
+        };
+        for group in groups {
+            match group.exertion(self)? {
+                Some(exertion) => {
+                    monitors
+                        .forces_and_moments
+                        .insert(group.name.clone(), exertion);
+                }
+                None => log::warn!("no monitor in group {}", group.name),
+            }
+        }
+        Ok(monitors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups() {
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0., 0.05];
+        for part in ["Cring_1", "Cring_2", "M1cell", "M1cov1", "M1level"] {
+            monitors.forces_and_moments.insert(
+                part.into(),
+                vec![Exertion::from(([0.; 3], ([1., 0., 0.], [0., 1., 0.]))); 2],
+            );
+        }
+        let grouped = monitors.groups(&MonitorGroups::mount()).unwrap();
+        assert_eq!(
+            grouped.forces_and_moments.keys().collect::<Vec<_>>(),
+            vec!["C-Rings", "M1 assembly", "M1 covers"]
+        );
+        assert_eq!(
+            grouped.forces_and_moments["C-Rings"][1].force,
+            Vector::from([2., 0., 0.])
+        );
+        assert_eq!(
+            grouped.forces_and_moments["M1 assembly"][0].force,
+            Vector::from([1., 0., 0.])
+        );
+
+        let groups: GroupsFile = toml::from_str(
+            r#"
+[[group]]
+name = "C-Rings"
+parts = { include = "Cring" }
+reference = [0.0, 0.0, 1.0]
+
+[[group]]
+name = "M1"
+parts = ["M1cell", "M1cov1", "M1cov2"]
+"#,
+        )
+        .unwrap();
+        let groups = MonitorGroups(groups.group);
+        assert_eq!(groups.names(), vec!["C-Rings", "M1"]);
+        let grouped = monitors.groups(&groups).unwrap();
+        // M - r x F = [0,2,0] - [0,0,1] x [2,0,0]
+        assert_eq!(
+            grouped.forces_and_moments["C-Rings"][0].moment,
+            Vector::from([0., 0., 0.])
+        );
+        assert_eq!(
+            grouped.forces_and_moments["M1"][0].force,
+            Vector::from([2., 0., 0.])
+        );
+        let subset = groups.get("M1").unwrap().subset(&monitors).unwrap();
+        assert_eq!(subset.forces_and_moments.len(), 2);
+    }
+}
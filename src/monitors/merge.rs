@@ -0,0 +1,291 @@
+//! Restarted CFD runs
+//!
+//! A restarted CFD run appends records whose time stamps overlap the ones already written,
+//! either in the same monitors file or in a new one.
+//! The records are split into segments of increasing time stamps and the overlapping
+//! intervals of the segments are merged according to a [MergePolicy].
+
+use super::{Exertion, Monitors, Series};
+use crate::Vector;
+
+/// Policy to merge the overlapping time intervals of restarted CFD runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keeps the records of the latest run
+    #[default]
+    KeepLatest,
+    /// Keeps the records of the first run
+    KeepFirst,
+}
+
+/// Missing records between two time stamps [s]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gap {
+    pub start: f64,
+    pub end: f64,
+}
+impl Gap {
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Returns the indices of the records to keep, in increasing time order
+///
+/// `time` is the time stamps in the order of the records in the monitors files
+pub(crate) fn merge_indices(time: &[f64], policy: MergePolicy) -> Vec<usize> {
+    let mut segments: Vec<std::ops::Range<usize>> = vec![];
+    let mut start = 0;
+    for i in 1..=time.len() {
+        if i == time.len() || time[i] <= time[i - 1] {
+            segments.push(start..i);
+            start = i;
+        }
+    }
+    let mut idx: Vec<usize> = vec![];
+    match policy {
+        MergePolicy::KeepLatest => {
+            let mut cutoff = f64::INFINITY;
+            for segment in segments.into_iter().rev() {
+                let first = time[segment.start];
+                idx.extend(segment.filter(|&i| time[i] < cutoff));
+                cutoff = cutoff.min(first);
+            }
+        }
+        MergePolicy::KeepFirst => {
+            let mut horizon = f64::NEG_INFINITY;
+            for segment in segments {
+                let last = time[segment.end - 1];
+                idx.extend(segment.filter(|&i| time[i] > horizon));
+                horizon = horizon.max(last);
+            }
+        }
+    }
+    idx.sort_by(|&a, &b| time[a].total_cmp(&time[b]));
+    idx
+}
+
+/// Returns the gaps larger than `tolerance` times the median sampling period
+pub(crate) fn gaps(time: &[f64], tolerance: f64) -> Vec<Gap> {
+    let Some(period) = crate::median_time_step(time) else {
+        return vec![];
+    };
+    time.windows(2)
+        .filter(|t| t[1] - t[0] > tolerance * period)
+        .map(|t| Gap {
+            start: t[0],
+            end: t[1],
+        })
+        .collect()
+}
+
+fn take<T: Clone>(values: &[T], idx: &[usize]) -> Vec<T> {
+    idx.iter().filter_map(|&i| values.get(i).cloned()).collect()
+}
+
+/// Linear interpolation
+trait Lerp: Clone {
+    fn lerp(&self, other: &Self, w: f64) -> Self;
+}
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, w: f64) -> Self {
+        self + (other - self) * w
+    }
+}
+impl Lerp for Option<f64> {
+    fn lerp(&self, other: &Self, w: f64) -> Self {
+        self.zip(*other).map(|(a, b)| a.lerp(&b, w))
+    }
+}
+impl Lerp for Vector {
+    fn lerp(&self, other: &Self, w: f64) -> Self {
+        Vector {
+            x: self.x.lerp(&other.x, w),
+            y: self.y.lerp(&other.y, w),
+            z: self.z.lerp(&other.z, w),
+        }
+    }
+}
+impl Lerp for Exertion {
+    fn lerp(&self, other: &Self, w: f64) -> Self {
+        Exertion {
+            force: self.force.lerp(&other.force, w),
+            moment: self.moment.lerp(&other.moment, w),
+            cop: None,
+        }
+    }
+}
+fn resample<T: Lerp>(time: &[f64], values: &[T], grid: &[f64]) -> Vec<T> {
+    grid.iter()
+        .map(|&t| {
+            let i = time.partition_point(|&x| x < t).clamp(1, time.len() - 1);
+            let w = (t - time[i - 1]) / (time[i] - time[i - 1]);
+            values[i - 1].lerp(&values[i], w)
+        })
+        .collect()
+}
+
+impl Series {
+    fn take(&mut self, idx: &[usize]) {
+        match self {
+            Series::Scalar(values) => *values = take(values, idx),
+            Series::Vector(values) => *values = take(values, idx),
+        }
+    }
+    fn resample(&mut self, time: &[f64], grid: &[f64]) {
+        match self {
+            Series::Scalar(values) => *values = resample(time, values, grid),
+            Series::Vector(values) => *values = resample(time, values, grid),
+        }
+    }
+}
+
+impl Monitors {
+    /// Appends the records of `other`
+    ///
+    /// Only the monitors that are in both `self` and `other` are kept
+    pub(crate) fn append(&mut self, mut other: Monitors) {
+        if self.is_empty() {
+            *self = other;
+            return;
+        }
+        self.time.append(&mut other.time);
+        self.heat_transfer_coefficients.retain(|key, values| {
+            other
+                .heat_transfer_coefficients
+                .get_mut(key)
+                .map(|other| values.append(other))
+                .is_some()
+        });
+        self.forces_and_moments.retain(|key, values| {
+            other
+                .forces_and_moments
+                .get_mut(key)
+                .map(|other| values.append(other))
+                .is_some()
+        });
+        self.quantities.retain(|key, quantity| {
+            let Some(other) = other.quantities.get_mut(key) else {
+                return false;
+            };
+            quantity.monitors.retain(|key, series| {
+                match (series, other.monitors.get_mut(key)) {
+                    (Series::Scalar(values), Some(Series::Scalar(other))) => values.append(other),
+                    (Series::Vector(values), Some(Series::Vector(other))) => values.append(other),
+                    _ => return false,
+                }
+                true
+            });
+            !quantity.monitors.is_empty()
+        });
+        self.total_forces_and_moments.clear();
+    }
+    fn take(&mut self, idx: &[usize]) {
+        self.time = take(&self.time, idx);
+        for values in self.heat_transfer_coefficients.values_mut() {
+            *values = take(values, idx);
+        }
+        for values in self.forces_and_moments.values_mut() {
+            *values = take(values, idx);
+        }
+        for quantity in self.quantities.values_mut() {
+            quantity
+                .monitors
+                .values_mut()
+                .for_each(|series| series.take(idx));
+        }
+        if !self.total_forces_and_moments.is_empty() {
+            self.total_forces_and_moments = take(&self.total_forces_and_moments, idx);
+        }
+    }
+    /// Sorts the records in increasing time order and removes the records of overlapping time intervals
+    /// according to the merge policy
+    pub fn repair(&mut self, policy: MergePolicy) -> &mut Self {
+        let idx = merge_indices(&self.time, policy);
+        if idx.len() != self.len() || idx.iter().enumerate().any(|(i, &j)| i != j) {
+            log::info!(
+                "repairing monitors time axis: {} records removed",
+                self.len() - idx.len()
+            );
+            self.take(&idx);
+        }
+        self
+    }
+    /// Returns the gaps in the time axis larger than `tolerance` times the median sampling period
+    pub fn gaps(&self, tolerance: f64) -> Vec<Gap> {
+        gaps(&self.time, tolerance)
+    }
+    /// Linearly interpolates the monitors onto a uniform time grid at the given sampling rate [Hz]
+    ///
+    /// The time axis must be increasing, see [Monitors::repair]
+    pub fn resample(&mut self, rate: f64) -> &mut Self {
+        let (Some(&start), Some(&end)) = (self.time.first(), self.time.last()) else {
+            return self;
+        };
+        if self.len() < 2 {
+            return self;
+        }
+        let n = 1 + ((end - start) * rate + 1e-6).floor() as usize;
+        let grid: Vec<f64> = (0..n).map(|i| start + i as f64 / rate).collect();
+        let time = &self.time;
+        for values in self.heat_transfer_coefficients.values_mut() {
+            *values = resample(time, values, &grid);
+        }
+        for values in self.forces_and_moments.values_mut() {
+            *values = resample(time, values, &grid);
+        }
+        for quantity in self.quantities.values_mut() {
+            quantity
+                .monitors
+                .values_mut()
+                .for_each(|series| series.resample(time, &grid));
+        }
+        if self.total_forces_and_moments.len() == time.len() {
+            self.total_forces_and_moments = resample(time, &self.total_forces_and_moments, &grid);
+        }
+        self.time = grid;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge() {
+        // first run up to 3s, restarted from 2s, restarted again from 2.5s
+        let time = [1., 2., 3., 2., 2.5, 2.5, 3., 4.];
+        assert_eq!(
+            merge_indices(&time, MergePolicy::KeepLatest),
+            vec![0, 3, 5, 6, 7]
+        );
+        assert_eq!(
+            merge_indices(&time, MergePolicy::KeepFirst),
+            vec![0, 1, 2, 7]
+        );
+
+        let mut monitors = Monitors::default();
+        monitors.time = time.to_vec();
+        monitors
+            .heat_transfer_coefficients
+            .insert("M2".into(), (0..time.len()).map(|i| i as f64).collect());
+        monitors.repair(MergePolicy::KeepLatest);
+        assert_eq!(monitors.time, vec![1., 2., 2.5, 3., 4.]);
+        assert_eq!(
+            monitors.heat_transfer_coefficients["M2"],
+            vec![0., 3., 5., 6., 7.]
+        );
+        assert!(monitors.gaps(1.5).is_empty());
+        assert_eq!(
+            monitors.gaps(0.9),
+            vec![Gap { start: 1., end: 2. }, Gap { start: 3., end: 4. }]
+        );
+        monitors.resample(2.);
+        assert_eq!(monitors.time, vec![1., 1.5, 2., 2.5, 3., 3.5, 4.]);
+        assert_eq!(
+            monitors.heat_transfer_coefficients["M2"],
+            vec![0., 1.5, 3., 5., 6., 6.5, 7.]
+        );
+    }
+}
@@ -1,21 +1,25 @@
 //! M1 and M2 segments center of pressure, forces and moments
 
+use super::merge::{merge_indices, MergePolicy};
 use crate::Vector;
-use crate::{Exertion, Monitors, MonitorsLoader};
+use crate::{decompress, Exertion, Monitors, MonitorsLoader};
 #[cfg(feature = "plot")]
 use plotters::prelude::*;
 use std::{
     collections::{BTreeMap, VecDeque},
-    fs::File,
     path::Path,
 };
 
+/// Time stamp and segments center of pressure, force and moment
+type Record = (f64, Vec<([f64; 3], ([f64; 3], [f64; 3]))>);
+
 /// Mirror data loader
 pub struct MirrorLoader<P: AsRef<Path>> {
     mirror: Mirror,
     path: P,
     time_range: (f64, f64),
     net_force: bool,
+    merge_policy: MergePolicy,
 }
 impl<P: AsRef<Path>> MirrorLoader<P> {
     fn new(mirror: Mirror, path: P) -> Self {
@@ -24,6 +28,7 @@ impl<P: AsRef<Path>> MirrorLoader<P> {
             path,
             time_range: (0f64, f64::INFINITY),
             net_force: false,
+            merge_policy: MergePolicy::default(),
         }
     }
     pub fn start_time(self, time: f64) -> Self {
@@ -44,6 +49,13 @@ impl<P: AsRef<Path>> MirrorLoader<P> {
             ..self
         }
     }
+    /// Sets the policy to merge the overlapping time intervals of restarted runs (default: [MergePolicy::KeepLatest])
+    pub fn merge_policy(self, merge_policy: MergePolicy) -> Self {
+        Self {
+            merge_policy,
+            ..self
+        }
+    }
     pub fn load(self) -> Result<Mirror, Box<dyn std::error::Error>> {
         let mut mirror = self.mirror;
         let (filename, time, force) = match &mut mirror {
@@ -51,74 +63,57 @@ impl<P: AsRef<Path>> MirrorLoader<P> {
             Mirror::M2 { time, force } => ("M2_segments_force.csv", time, force),
         };
         let path = Path::new(self.path.as_ref());
-        if let Ok(csv_file) = File::open(&path.join(filename)) {
-            let mut rdr = csv::Reader::from_reader(csv_file);
-            for result in rdr.deserialize() {
-                let record: (f64, Vec<([f64; 3], ([f64; 3], [f64; 3]))>) = result?;
-                let t = record.0;
-                if t < self.time_range.0 - 1. / 40. || t > self.time_range.1 + 1. / 40. {
-                    continue;
-                };
-                let mut record_iter = record.1.into_iter();
-                if let Some(t_b) = time.back() {
-                    if t >= *t_b {
-                        time.push_back(t);
-                        for fm in force.values_mut() {
-                            fm.push_back(record_iter.next().unwrap().into())
-                        }
-                    } else {
-                        if let Some(index) = time.iter().rposition(|&x| x < t) {
-                            time.insert(index + 1, t);
-                            for fm in force.values_mut() {
-                                fm.insert(index + 1, record_iter.next().unwrap().into())
-                            }
-                        } else {
-                            time.push_front(t);
-                            for fm in force.values_mut() {
-                                fm.push_front(record_iter.next().unwrap().into())
-                            }
-                        }
-                    }
-                } else {
-                    time.push_back(t);
-                    for fm in force.values_mut() {
-                        fm.push_back(record_iter.next().unwrap().into())
-                    }
-                }
+        let csv_file = decompress::find(path.join(filename), decompress::CSV_EXTENSIONS)?;
+        let mut rdr = csv::Reader::from_reader(decompress::open(csv_file)?);
+        let mut records: Vec<Record> = vec![];
+        for result in rdr.deserialize() {
+            let record: Record = result?;
+            let t = record.0;
+            if t < self.time_range.0 - 1. / 40. || t > self.time_range.1 + 1. / 40. {
+                continue;
+            };
+            records.push(record);
+        }
+        let record_time: Vec<f64> = records.iter().map(|(t, _)| *t).collect();
+        let mut records: Vec<_> = records.into_iter().map(Some).collect();
+        for i in merge_indices(&record_time, self.merge_policy) {
+            let (t, record) = records[i].take().unwrap();
+            time.push_back(t);
+            let mut record_iter = record.into_iter();
+            for fm in force.values_mut() {
+                fm.push_back(record_iter.next().unwrap().into())
             }
-            if self.net_force {
-                if let Mirror::M1 { time, force } = &mut mirror {
-                    let ts = *time.front().unwrap();
-                    let te = *time.back().unwrap();
-                    let monitors = MonitorsLoader::<2021>::default()
-                        .data_path(path)
-                        .header_filter("M1cell".to_string())
-                        .start_time(ts)
-                        .end_time(te)
-                        .load()?;
-                    let m1_cell = &monitors.forces_and_moments["M1cell"];
-                    assert_eq!(
-                        time.len(),
-                        m1_cell.len(),
-                        "{:?} {:?}/{:?}: M1 segments and M1 cell # of sample do not match",
-                        (ts, te),
-                        (monitors.time[0], monitors.time.last().unwrap()),
-                        path
-                    );
-                    for v in force.values_mut() {
-                        for (e, cell) in v.iter_mut().zip(m1_cell) {
-                            let mut f = &mut e.force;
-                            f += &(&cell.force / 7f64).unwrap();
-                            let mut m = &mut e.moment;
-                            m += &(&cell.moment / 7f64).unwrap();
-                        }
+        }
+        if self.net_force {
+            if let Mirror::M1 { time, force } = &mut mirror {
+                let ts = *time.front().unwrap();
+                let te = *time.back().unwrap();
+                let monitors = MonitorsLoader::<2021>::default()
+                    .data_path(path)
+                    .header_filter("M1cell".to_string())
+                    .start_time(ts)
+                    .end_time(te)
+                    .load()?;
+                let m1_cell = &monitors.forces_and_moments["M1cell"];
+                assert_eq!(
+                    time.len(),
+                    m1_cell.len(),
+                    "{:?} {:?}/{:?}: M1 segments and M1 cell # of sample do not match",
+                    (ts, te),
+                    (monitors.time[0], monitors.time.last().unwrap()),
+                    path
+                );
+                for v in force.values_mut() {
+                    for (e, cell) in v.iter_mut().zip(m1_cell) {
+                        let mut f = &mut e.force;
+                        f += &(&cell.force / 7f64).unwrap();
+                        let mut m = &mut e.moment;
+                        m += &(&cell.moment / 7f64).unwrap();
                     }
                 }
             }
-            Ok(mirror)
-        } else {
-            Err(format!("Cannot open {:?}", &path).into())
         }
+        Ok(mirror)
     }
 }
 
@@ -494,3 +489,41 @@ impl Mirror {
             .unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn compressed() {
+        let dir = std::env::temp_dir().join(format!("mirror-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let err = Mirror::m2(&dir).load().unwrap_err();
+        assert!(err
+            .downcast_ref::<decompress::DecompressError>()
+            .is_some_and(|e| matches!(e, decompress::DecompressError::NotFound(..))));
+
+        let row = |t: f64| {
+            std::iter::once(t.to_string())
+                .chain((0..63).map(|i| (i % 9).to_string()))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(dir.join("M2_segments_force.csv.gz")).unwrap(),
+            Default::default(),
+        );
+        // the first row stands for the header
+        writeln!(encoder, "{}\n{}\n{}", row(-1.), row(0.), row(0.05)).unwrap();
+        encoder.finish().unwrap();
+        let m2 = Mirror::m2(&dir).load().unwrap();
+        assert_eq!(m2.forces_and_moments()["S7"].len(), 2);
+        assert_eq!(
+            m2.forces_and_moments()["S1"][1].moment,
+            Vector::from([6., 7., 8.])
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,16 +1,41 @@
+mod cache;
+mod catalog;
+pub mod export;
+mod frame;
+mod groups;
+mod merge;
 mod mirror;
+mod nodes;
+mod parser;
+mod parts;
+mod quantity;
 mod reports;
+mod selection;
+mod snapshot;
+mod stats;
+mod validation;
+#[cfg(feature = "windloading")]
+pub mod windloading;
 
 use std::path::PathBuf;
 
+pub use catalog::{Components, MonitorEntry, MonitorKind, MonitorsCatalog};
+pub use frame::{Frame, FrameError, Frames};
+pub use groups::{GroupError, MonitorGroup, MonitorGroups};
+pub use merge::{Gap, MergePolicy};
 pub use mirror::Mirror;
+pub use nodes::{FemNodes, NodesError, PartNodes};
+pub use parts::PartSelection;
+pub use quantity::{Quantity, Series};
 pub use reports::{Exertion, Monitors, MonitorsLoader};
+pub use snapshot::{IntoSnapshots, Snapshot, Snapshots};
+pub use stats::{ExertionStats, MonitorsStats, Percentiles, SeriesStats, Stats, VectorStats};
+pub use validation::{PartReport, Validation, ValidationReport};
 
 #[derive(thiserror::Error, Debug)]
 pub enum MonitorsError {
     #[error("Failed to decompress the monitor file")]
-    #[cfg(feature = "bzip2")]
-    Decompress(#[from] bzip2::Error),
+    Decompress(#[from] crate::decompress::DecompressError),
     #[error("Failed to read the monitor file: {1}")]
     Io(#[source] std::io::Error, PathBuf),
     #[error("Failed to deserialize the CSV file")]
@@ -21,6 +46,10 @@ pub enum MonitorsError {
     Regex(#[from] regex::Error),
     #[error("Entry {0} not found in Map")]
     MissingEntry(String),
+    #[error("the monitor header pattern {0:?} has no `monitor` capture group")]
+    Pattern(String),
     #[error("expected year {0}, found {1}")]
     YearMismatch(u32, u32),
+    #[error("invalid monitors:\n{0}")]
+    Validation(ValidationReport),
 }
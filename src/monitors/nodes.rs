@@ -0,0 +1,240 @@
+//! FEM nodes
+//!
+//! [FemNodes] are the locations of the FEM nodes, in the OSS, where the parts of the telescope are attached
+//! and about which the moments of the parts are evaluated by [Monitors::into_local].
+//! Each part may have several named reference points, the moments are transferred to the [FemNodes::NODE] point by default.
+//!
+//! The nodes are loaded with [FemNodes::from_path] either from a CSV file with the columns `monitor,point,x,y,z`:
+//! ```csv
+//! monitor,point,x,y,z
+//! M1cov1,node,0.0,13.562,5.3064
+//! M1cov1,edge,0.0,14.5,5.3064
+//! ```
+//! or from a JSON (or TOML) file:
+//! ```json
+//! {"M1cov1": {"node": [0.0, 13.562, 5.3064], "edge": [0.0, 14.5, 5.3064]}}
+//! ```
+
+use super::Monitors;
+use crate::{config, Vector};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum NodesError {
+    #[error("failed to read the FEM nodes file {1:?}")]
+    Io(#[source] std::io::Error, PathBuf),
+    #[error("failed to parse the CSV FEM nodes file")]
+    Csv(#[from] csv::Error),
+    #[error("failed to load the FEM nodes file")]
+    Config(#[from] crate::config::ConfigError),
+    #[error("no FEM node {1:?} for monitor {0}")]
+    MissingNode(String, String),
+}
+type Result<T> = std::result::Result<T, NodesError>;
+
+/// Named reference points of a part
+pub type PartNodes = BTreeMap<String, Vector>;
+
+#[derive(Deserialize)]
+struct NodeRecord {
+    monitor: String,
+    point: String,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+/// FEM nodes keyed by monitor name
+#[derive(Debug, Clone)]
+pub struct FemNodes(BTreeMap<String, PartNodes>);
+impl Deref for FemNodes {
+    type Target = BTreeMap<String, PartNodes>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl DerefMut for FemNodes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl Default for FemNodes {
+    /// M1 covers nodes
+    fn default() -> Self {
+        let mut fem = FemNodes::new();
+        fem.insert_node("M1cov1", Self::NODE, [0., 13.5620, 5.3064]);
+        fem.insert_node("M1cov2", Self::NODE, [11.7451, 6.7810, 5.3064]);
+        fem.insert_node("M1cov3", Self::NODE, [11.7451, -6.7810, 5.3064]);
+        fem.insert_node("M1cov4", Self::NODE, [0., -13.5621, 5.3064]);
+        fem.insert_node("M1cov5", Self::NODE, [-11.7451, -6.7810, 5.3064]);
+        fem.insert_node("M1cov6", Self::NODE, [-11.7451, 6.7810, 5.3064]);
+        fem.insert_node("M1covin1", Self::NODE, [2.3650, 4.0963, 4.7000]);
+        fem.insert_node("M1covin2", Self::NODE, [4.3000, 0., 4.7000]);
+        fem.insert_node("M1covin3", Self::NODE, [2.3650, -4.0963, 4.7000]);
+        fem.insert_node("M1covin4", Self::NODE, [-2.3650, -4.0963, 4.7000]);
+        fem.insert_node("M1covin5", Self::NODE, [-4.3000, 0., 4.7000]);
+        fem.insert_node("M1covin6", Self::NODE, [-2.3650, 4.0963, 4.7000]);
+        fem
+    }
+}
+impl FemNodes {
+    /// Default reference point name
+    pub const NODE: &'static str = "node";
+    /// Creates an empty node table
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+    /// Loads the nodes from a CSV, a TOML or a JSON file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut fem = FemNodes::new();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => {
+                let file = File::open(path).map_err(|e| NodesError::Io(e, path.to_path_buf()))?;
+                for record in csv::Reader::from_reader(file).deserialize() {
+                    let NodeRecord {
+                        monitor,
+                        point,
+                        x,
+                        y,
+                        z,
+                    } = record?;
+                    fem.insert_node(monitor, point, [x, y, z]);
+                }
+            }
+            _ => {
+                let nodes: BTreeMap<String, BTreeMap<String, [f64; 3]>> = config::from_path(path)?;
+                for (monitor, points) in nodes {
+                    for (point, xyz) in points {
+                        fem.insert_node(monitor.clone(), point, xyz);
+                    }
+                }
+            }
+        }
+        Ok(fem)
+    }
+    /// Inserts the reference point `point` of monitor `monitor`
+    pub fn insert_node<S: Into<String>, T: Into<String>>(
+        &mut self,
+        monitor: S,
+        point: T,
+        xyz: [f64; 3],
+    ) -> &mut Self {
+        self.0
+            .entry(monitor.into())
+            .or_default()
+            .insert(point.into(), xyz.into());
+        self
+    }
+    /// Returns the reference point `point` of monitor `monitor`
+    pub fn node(&self, monitor: &str, point: &str) -> Option<&Vector> {
+        self.get(monitor).and_then(|points| points.get(point))
+    }
+}
+
+impl Monitors {
+    /// Transfers the moments from the OSS origin to the M1 covers FEM nodes, see [FemNodes::default]
+    ///
+    /// The monitors without a node are left in the OSS with a warning
+    pub fn into_local(&mut self) -> &mut Self {
+        self.into_local_with(&FemNodes::default(), FemNodes::NODE)
+    }
+    /// Transfers the moments from the OSS origin to the reference point `point` of each monitor
+    ///
+    /// The monitors without a reference point are left in the OSS with a warning
+    pub fn into_local_with(&mut self, nodes: &FemNodes, point: &str) -> &mut Self {
+        for (key, value) in self.forces_and_moments.iter_mut() {
+            match nodes.node(key, point) {
+                Some(node) => value.iter_mut().for_each(|v| {
+                    v.into_local(node.clone());
+                }),
+                None => log::warn!(
+                    "no FEM node {point:?} for monitor {key}, moments are left in the OSS"
+                ),
+            }
+        }
+        self
+    }
+    /// Transfers the moments from the OSS origin to the reference point `point` of each monitor
+    ///
+    /// Returns an error, leaving the monitors untouched, if any monitor is missing a reference point
+    pub fn try_into_local_with(&mut self, nodes: &FemNodes, point: &str) -> Result<&mut Self> {
+        if let Some(key) = self
+            .forces_and_moments
+            .keys()
+            .find(|key| nodes.node(key, point).is_none())
+        {
+            return Err(NodesError::MissingNode(key.clone(), point.to_string()));
+        }
+        Ok(self.into_local_with(nodes, point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitors::Exertion;
+
+    #[test]
+    fn nodes() {
+        let dir = std::env::temp_dir().join(format!("nodes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("nodes.csv");
+        std::fs::write(
+            &path,
+            "monitor,point,x,y,z\nCring_1,node,0,0,1\nCring_1,top,0,0,2\nGIR,node,1,0,0\n",
+        )
+        .unwrap();
+        let nodes = FemNodes::from_path(&path).unwrap();
+        assert_eq!(nodes["Cring_1"].len(), 2);
+        assert_eq!(
+            nodes.node("Cring_1", "top"),
+            Some(&Vector::from([0., 0., 2.]))
+        );
+
+        let path = dir.join("nodes.json");
+        std::fs::write(
+            &path,
+            r#"{"Cring_1": {"node": [0, 0, 1], "top": [0, 0, 2]}, "GIR": {"node": [1, 0, 0]}}"#,
+        )
+        .unwrap();
+        let json_nodes = FemNodes::from_path(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(json_nodes.0, nodes.0);
+
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0.];
+        for part in ["Cring_1", "GIR", "Tbot"] {
+            monitors.forces_and_moments.insert(
+                part.into(),
+                vec![Exertion::from(([0.; 3], ([1., 0., 0.], [0.; 3])))],
+            );
+        }
+        assert!(matches!(
+            monitors.try_into_local_with(&nodes, FemNodes::NODE),
+            Err(NodesError::MissingNode(key, _)) if key == "Tbot"
+        ));
+        assert_eq!(
+            monitors.forces_and_moments["Cring_1"][0].moment,
+            Vector::zero()
+        );
+        monitors.into_local_with(&nodes, FemNodes::NODE);
+        // M - r x F = -[0,0,1] x [1,0,0]
+        assert_eq!(
+            monitors.forces_and_moments["Cring_1"][0].moment,
+            Vector::from([0., -1., 0.])
+        );
+        assert_eq!(monitors.forces_and_moments["GIR"][0].moment, Vector::zero());
+        assert_eq!(
+            monitors.forces_and_moments["Tbot"][0].moment,
+            Vector::zero()
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! Streaming monitors parser
+//!
+//! The monitors columns are matched once against the header regular expressions,
+//! the records are then read one at a time and only the selected columns are parsed.
+
+use super::{
+    catalog::{Components, MonitorEntry, MonitorKind, MonitorsCatalog},
+    quantity::{QuantityPattern, Series},
+    Exertion, Monitors, MonitorsError, Quantity,
+};
+use crate::Vector;
+use regex::Regex;
+use std::{collections::BTreeMap, io::Read};
+
+type Result<T> = std::result::Result<T, MonitorsError>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Axis {
+    X,
+    Y,
+    Z,
+}
+impl Axis {
+    fn new(axis: &str) -> Option<Self> {
+        match axis {
+            "X" | "x" => Some(Axis::X),
+            "Y" | "y" => Some(Axis::Y),
+            "Z" | "z" => Some(Axis::Z),
+            _ => None,
+        }
+    }
+    /// Sets the component of the last vector or pushes a new vector if the component is already set
+    fn push(self, vectors: &mut Vec<Vector>, value: f64) {
+        let slot = vectors.last_mut().map(|v| match self {
+            Axis::X => &mut v.x,
+            Axis::Y => &mut v.y,
+            Axis::Z => &mut v.z,
+        });
+        match slot {
+            Some(slot) if slot.is_none() => *slot = Some(value),
+            _ => vectors.push(match self {
+                Axis::X => Vector::from_x(value),
+                Axis::Y => Vector::from_y(value),
+                Axis::Z => Vector::from_z(value),
+            }),
+        }
+    }
+}
+/// Force or moment component of an [Exertion]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Component {
+    Force(Axis),
+    Moment(Axis),
+}
+impl Component {
+    /// Sets the component of the last exertion or pushes a new exertion if the component is already set
+    fn push(self, exertions: &mut Vec<Exertion>, value: f64) {
+        if exertions.is_empty() {
+            exertions.push(Exertion::default());
+        }
+        let exertion = exertions.last_mut().unwrap();
+        let (slot, new): (&mut Option<f64>, fn(f64) -> Exertion) = match self {
+            Component::Force(Axis::X) => (&mut exertion.force.x, Exertion::from_force_x),
+            Component::Force(Axis::Y) => (&mut exertion.force.y, Exertion::from_force_y),
+            Component::Force(Axis::Z) => (&mut exertion.force.z, Exertion::from_force_z),
+            Component::Moment(Axis::X) => (&mut exertion.moment.x, Exertion::from_moment_x),
+            Component::Moment(Axis::Y) => (&mut exertion.moment.y, Exertion::from_moment_y),
+            Component::Moment(Axis::Z) => (&mut exertion.moment.z, Exertion::from_moment_z),
+        };
+        if slot.is_some() {
+            exertions.push(new(value));
+        } else {
+            *slot = Some(value);
+        }
+    }
+}
+/// Destination of a monitors column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Column {
+    /// Index of the heat transfer coefficient
+    Htc(usize),
+    /// Index of the exertion and its component
+    Exertion(usize, Component),
+    /// Index of the quantity monitor and the axis of vector monitors
+    Quantity(usize, Option<Axis>),
+}
+
+/// Header regular expressions of the monitors
+pub(crate) struct Patterns {
+    pub htc: Option<Regex>,
+    pub force: Regex,
+    pub moment: Regex,
+    pub quantities: Vec<QuantityPattern>,
+}
+impl Patterns {
+    /// Star-CCM+ monitors (2021 and 2025 CFD campaigns)
+    pub fn star_ccm() -> Result<Self> {
+        Ok(Self {
+            htc: Some(Regex::new(
+                r"(\w+) Monitor: Surface Average of Heat Transfer Coefficient \(W/m\^2-K\)",
+            )?),
+            //Cabs_X Monitor 2: Force (N)
+            force: Regex::new(r"(.+)_([XYZ]) Monitor: Force \(N\)")?,
+            moment: Regex::new(r"(.+)Mom_([XYZ]) Monitor: Moment \(N-m\)")?,
+            quantities: vec![QuantityPattern::star_ccm()?],
+        })
+    }
+    /// 2020 CFD campaign monitors, with the whitespaces removed from the headers
+    pub fn b2020() -> Result<Self> {
+        Ok(Self {
+            htc: None,
+            force: Regex::new(r"Force(\w+)([xyz])Monitor:Force\(N\)")?,
+            moment: Regex::new(r"Moment(\w+)([xyz])Monitor:Moment\(N-m\)")?,
+            quantities: vec![],
+        })
+    }
+    /// Adds the header patterns `(quantity, regex)`, they take precedence over the default patterns
+    pub fn register(mut self, patterns: &[(String, String)]) -> Result<Self> {
+        let registered = patterns
+            .iter()
+            .map(|(quantity, regex)| QuantityPattern::new(quantity, regex))
+            .collect::<Result<Vec<_>>>()?;
+        self.quantities.splice(0..0, registered);
+        Ok(self)
+    }
+}
+
+/// Monitors columns selected from the header
+#[derive(Debug, Default)]
+pub(crate) struct Columns {
+    htc_keys: Vec<String>,
+    exertion_keys: Vec<String>,
+    quantity_keys: Vec<(String, String)>,
+    vector_quantities: Vec<bool>,
+    units: BTreeMap<String, String>,
+    columns: Vec<(usize, Column)>,
+}
+impl Columns {
+    /// Selects the columns whose header matches `header` but not `exclude`
+    ///
+    /// The first column (time) is never selected
+    pub fn new(
+        headers: &[String],
+        patterns: &Patterns,
+        header: &Regex,
+        exclude: Option<&Regex>,
+    ) -> Self {
+        let mut this = Columns::default();
+        let mut htc_idx: BTreeMap<String, usize> = BTreeMap::new();
+        let mut exertion_idx: BTreeMap<String, usize> = BTreeMap::new();
+        let mut quantity_idx: BTreeMap<(String, String), usize> = BTreeMap::new();
+        for (i, h) in headers.iter().enumerate().skip(1) {
+            if !header.is_match(h) || exclude.is_some_and(|x| x.is_match(h)) {
+                continue;
+            }
+            let mut matched = false;
+            if let Some(capts) = patterns.htc.as_ref().and_then(|re| re.captures(h)) {
+                let key = capts.get(1).unwrap().as_str();
+                let k = index_of(&mut htc_idx, &mut this.htc_keys, key.to_owned());
+                this.columns.push((i, Column::Htc(k)));
+                matched = true;
+            }
+            let component = if let Some(capts) = patterns.force.captures(h) {
+                Axis::new(&capts[2]).map(|axis| (capts[1].to_owned(), Component::Force(axis)))
+            } else if let Some(capts) = patterns.moment.captures(h) {
+                Axis::new(&capts[2]).map(|axis| {
+                    (
+                        capts[1].trim_end_matches('_').to_owned(),
+                        Component::Moment(axis),
+                    )
+                })
+            } else {
+                None
+            };
+            if let Some((key, component)) = component {
+                let k = index_of(&mut exertion_idx, &mut this.exertion_keys, key);
+                this.columns.push((i, Column::Exertion(k, component)));
+                matched = true;
+            }
+            if matched {
+                continue;
+            }
+            let Some(capture) = patterns.quantities.iter().find_map(|p| p.captures(h)) else {
+                continue;
+            };
+            let axis = capture.axis.and_then(Axis::new);
+            let key = (capture.quantity, capture.monitor.to_owned());
+            if let Some(unit) = capture.unit {
+                this.units.entry(key.0.clone()).or_insert(unit);
+            }
+            let k = index_of(&mut quantity_idx, &mut this.quantity_keys, key);
+            if k == this.vector_quantities.len() {
+                this.vector_quantities.push(axis.is_some());
+            }
+            if this.vector_quantities[k] == axis.is_some() {
+                this.columns.push((i, Column::Quantity(k, axis)));
+            }
+        }
+        this
+    }
+    /// Returns the catalog of the selected monitors
+    pub fn catalog(&self) -> MonitorsCatalog {
+        let mut entries: Vec<MonitorEntry> = vec![];
+        let mut idx: BTreeMap<(String, MonitorKind), usize> = BTreeMap::new();
+        for (_, column) in &self.columns {
+            let (part, kind, unit, axis) = match *column {
+                Column::Htc(k) => (
+                    &self.htc_keys[k],
+                    MonitorKind::HeatTransferCoefficient,
+                    Some("W/m^2-K"),
+                    None,
+                ),
+                Column::Exertion(k, Component::Force(axis)) => (
+                    &self.exertion_keys[k],
+                    MonitorKind::Force,
+                    Some("N"),
+                    Some(axis),
+                ),
+                Column::Exertion(k, Component::Moment(axis)) => (
+                    &self.exertion_keys[k],
+                    MonitorKind::Moment,
+                    Some("N-m"),
+                    Some(axis),
+                ),
+                Column::Quantity(k, axis) => {
+                    let (quantity, monitor) = &self.quantity_keys[k];
+                    (
+                        monitor,
+                        MonitorKind::Other(quantity.clone()),
+                        self.units.get(quantity).map(String::as_str),
+                        axis,
+                    )
+                }
+            };
+            let i = *idx.entry((part.clone(), kind.clone())).or_insert_with(|| {
+                entries.push(MonitorEntry {
+                    part: part.clone(),
+                    kind,
+                    unit: unit.map(String::from),
+                    components: Components::default(),
+                });
+                entries.len() - 1
+            });
+            let components = &mut entries[i].components;
+            match axis {
+                Some(Axis::X) => components.x = true,
+                Some(Axis::Y) => components.y = true,
+                Some(Axis::Z) => components.z = true,
+                None => (),
+            }
+        }
+        MonitorsCatalog(entries)
+    }
+    /// Reads the records and returns the monitors within the time range
+    pub fn stream<R: Read>(
+        &self,
+        rdr: &mut csv::Reader<R>,
+        time_range: (f64, f64),
+    ) -> Result<Monitors> {
+        let mut monitors = Monitors::default();
+        let mut htc: Vec<Vec<f64>> = vec![vec![]; self.htc_keys.len()];
+        let mut exertions: Vec<Vec<Exertion>> = vec![vec![]; self.exertion_keys.len()];
+        let mut quantities: Vec<Series> = self
+            .vector_quantities
+            .iter()
+            .map(|&is_vector| {
+                if is_vector {
+                    Series::Vector(vec![])
+                } else {
+                    Series::Scalar(vec![])
+                }
+            })
+            .collect();
+        let mut record = csv::StringRecord::new();
+        while rdr.read_record(&mut record)? {
+            let time = record.get(0).unwrap_or_default().parse::<f64>()?;
+            if time < time_range.0 - 1. / 40. || time > time_range.1 + 1. / 40. {
+                continue;
+            };
+            monitors.time.push(time);
+            for (i, column) in &self.columns {
+                let Some(data) = record.get(*i) else {
+                    continue;
+                };
+                let value = data.parse::<f64>()?;
+                match *column {
+                    Column::Htc(k) => htc[k].push(value.abs()),
+                    Column::Exertion(k, component) => component.push(&mut exertions[k], value),
+                    Column::Quantity(k, axis) => match (&mut quantities[k], axis) {
+                        (Series::Scalar(values), None) => values.push(value),
+                        (Series::Vector(values), Some(axis)) => axis.push(values, value),
+                        _ => (),
+                    },
+                }
+            }
+        }
+        monitors.heat_transfer_coefficients = self
+            .htc_keys
+            .iter()
+            .cloned()
+            .zip(htc)
+            .filter(|(_, values)| !values.is_empty())
+            .collect();
+        monitors.forces_and_moments = self
+            .exertion_keys
+            .iter()
+            .cloned()
+            .zip(exertions)
+            .filter(|(_, values)| !values.is_empty())
+            .collect();
+        for ((quantity, monitor), series) in self.quantity_keys.iter().zip(quantities) {
+            if series.is_empty() {
+                continue;
+            }
+            monitors
+                .quantities
+                .entry(quantity.clone())
+                .or_insert_with(|| Quantity {
+                    unit: self.units.get(quantity).cloned(),
+                    ..Default::default()
+                })
+                .monitors
+                .insert(monitor.clone(), series);
+        }
+        Ok(monitors)
+    }
+}
+fn index_of<K: Ord + Clone>(idx: &mut BTreeMap<K, usize>, keys: &mut Vec<K>, key: K) -> usize {
+    *idx.entry(key.clone()).or_insert_with(|| {
+        keys.push(key);
+        keys.len() - 1
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream() {
+        let csv = "\
+Time,M1cov1_X Monitor: Force (N),M1cov1_Y Monitor: Force (N),M1cov1_Z Monitor: Force (N),M1cov1Mom_X Monitor: Moment (N-m),M1cov1Mom_Y Monitor: Moment (N-m),M1cov1Mom_Z Monitor: Moment (N-m),M2 Monitor: Surface Average of Heat Transfer Coefficient (W/m^2-K),Floor_X Monitor: Force (N)
+0.5,1,2,3,4,5,6,-7,8
+1.0,10,20,30,40,50,60,70,80
+1.5,11,21,31,41,51,61,71,81
+";
+        let mut rdr = csv::Reader::from_reader(csv.as_bytes());
+        let headers: Vec<String> = rdr.headers().unwrap().iter().map(String::from).collect();
+        let columns = Columns::new(
+            &headers,
+            &Patterns::star_ccm().unwrap(),
+            &Regex::new(r"\w+").unwrap(),
+            Some(&Regex::new("Floor").unwrap()),
+        );
+        let catalog = columns.catalog();
+        assert_eq!(catalog.parts(), vec!["M1cov1", "M2"]);
+        let force = catalog.kind(MonitorKind::Force).next().unwrap();
+        assert_eq!(force.unit.as_deref(), Some("N"));
+        assert_eq!(force.components.to_string(), "XYZ");
+        assert!(catalog
+            .kind(MonitorKind::HeatTransferCoefficient)
+            .all(|e| e.components.is_scalar()));
+        let monitors = columns.stream(&mut rdr, (0.75, 1.5)).unwrap();
+        assert_eq!(monitors.time, vec![1.0, 1.5]);
+        assert_eq!(monitors.heat_transfer_coefficients["M2"], vec![70., 71.]);
+        assert!(!monitors.forces_and_moments.contains_key("Floor"));
+        let m1cov1 = &monitors.forces_and_moments["M1cov1"];
+        assert_eq!(m1cov1.len(), 2);
+        assert_eq!(m1cov1[1].force.y, Some(21.));
+        assert_eq!(m1cov1[1].moment.z, Some(61.));
+    }
+
+    #[test]
+    fn quantities() {
+        let csv = "\
+Time,M1 Monitor: Surface Average of Temperature (K),probe1_X Monitor: Velocity (m/s),probe1_Y Monitor: Velocity (m/s),p1 Probe: Static Pressure [Pa]
+0.5,280,1,2,100
+1.0,281,3,4,101
+";
+        let mut rdr = csv::Reader::from_reader(csv.as_bytes());
+        let headers: Vec<String> = rdr.headers().unwrap().iter().map(String::from).collect();
+        let patterns = Patterns::star_ccm()
+            .unwrap()
+            .register(&[(
+                "Static Pressure".into(),
+                r"(?P<monitor>\w+) Probe: Static Pressure \[(?P<unit>.+)\]".into(),
+            )])
+            .unwrap();
+        let columns = Columns::new(&headers, &patterns, &Regex::new(r"\w+").unwrap(), None);
+        let monitors = columns.stream(&mut rdr, (0., f64::INFINITY)).unwrap();
+
+        let temperature = &monitors.quantities["Surface Average of Temperature"];
+        assert_eq!(temperature.unit.as_deref(), Some("K"));
+        assert_eq!(
+            temperature.monitors["M1"].as_scalar(),
+            Some([280., 281.].as_slice())
+        );
+        let velocity = &monitors.quantities["Velocity"];
+        assert_eq!(velocity.unit.as_deref(), Some("m/s"));
+        let probe1 = velocity.monitors["probe1"].as_vector().unwrap();
+        assert_eq!(probe1.len(), 2);
+        assert_eq!(
+            (probe1[1].x, probe1[1].y, probe1[1].z),
+            (Some(3.), Some(4.), None)
+        );
+        let pressure = &monitors.quantities["Static Pressure"];
+        assert_eq!(pressure.unit.as_deref(), Some("Pa"));
+        assert_eq!(
+            pressure.monitors["p1"].as_scalar(),
+            Some([100., 101.].as_slice())
+        );
+        assert!(Patterns::star_ccm()
+            .unwrap()
+            .register(&[("Pressure".into(), r"\w+ Probe".into())])
+            .is_err());
+    }
+}
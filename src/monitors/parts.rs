@@ -0,0 +1,48 @@
+//! Monitored parts selection
+//!
+//! A [PartSelection] picks monitored parts by name, either with regular expressions or from a list,
+//! see the [monitor groups](super::MonitorGroup).
+
+use regex::Regex;
+use serde::Deserialize;
+
+/// Selection of monitored parts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PartSelection {
+    /// Parts whose name matches `include` but not `exclude`, in alphabetical order
+    Matching {
+        include: String,
+        exclude: Option<String>,
+    },
+    /// Given parts, in the given order
+    List(Vec<String>),
+}
+impl PartSelection {
+    /// Returns the names of the selected parts among the given ones
+    ///
+    /// The parts of a [PartSelection::List] that are not in `parts` are skipped
+    pub fn select<'a, I>(&self, parts: I) -> std::result::Result<Vec<String>, regex::Error>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let parts: Vec<&String> = parts.into_iter().collect();
+        Ok(match self {
+            PartSelection::Matching { include, exclude } => {
+                let include = Regex::new(include)?;
+                let exclude = exclude.as_deref().map(Regex::new).transpose()?;
+                let mut selection: Vec<String> = parts
+                    .into_iter()
+                    .filter(|p| include.is_match(p))
+                    .filter(|p| !exclude.as_ref().is_some_and(|x| x.is_match(p)))
+                    .cloned()
+                    .collect();
+                selection.sort();
+                selection
+            }
+            PartSelection::List(list) => {
+                list.iter().filter(|p| parts.contains(p)).cloned().collect()
+            }
+        })
+    }
+}
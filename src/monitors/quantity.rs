@@ -0,0 +1,122 @@
+//! Generic monitor quantities
+//!
+//! Star-CCM+ monitors headers are written as `<monitor> Monitor: <quantity> (<unit>)`,
+//! e.g. `M1 Monitor: Surface Average of Temperature (K)`,
+//! and the axis of the components of vector monitors is appended to the monitor name,
+//! e.g. `probe1_X Monitor: Velocity (m/s)`.
+//!
+//! The monitors that are neither forces, moments nor heat transfer coefficients are gathered
+//! per [Quantity] into [Monitors::quantities](super::Monitors::quantities).
+//! Headers with a different layout are parsed by registering a header pattern with
+//! [MonitorsLoader::quantity](super::MonitorsLoader::quantity).
+
+use super::MonitorsError;
+use crate::Vector;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// Time series of a monitor
+#[derive(Debug, Clone, PartialEq)]
+pub enum Series {
+    Scalar(Vec<f64>),
+    Vector(Vec<Vector>),
+}
+impl Series {
+    pub fn len(&self) -> usize {
+        match self {
+            Series::Scalar(values) => values.len(),
+            Series::Vector(values) => values.len(),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the scalar values, `None` for a vector monitor
+    pub fn as_scalar(&self) -> Option<&[f64]> {
+        match self {
+            Series::Scalar(values) => Some(values),
+            Series::Vector(_) => None,
+        }
+    }
+    /// Returns the vector values, `None` for a scalar monitor
+    pub fn as_vector(&self) -> Option<&[Vector]> {
+        match self {
+            Series::Scalar(_) => None,
+            Series::Vector(values) => Some(values),
+        }
+    }
+}
+
+/// Monitors of the same physical quantity
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Quantity {
+    /// Unit as written in the monitors headers, e.g. `W/m^2-K`
+    pub unit: Option<String>,
+    /// Time series of each monitor
+    pub monitors: BTreeMap<String, Series>,
+}
+
+/// Quantity, monitor, axis and unit captured from a monitor header
+pub(crate) struct Capture<'a> {
+    pub quantity: String,
+    pub monitor: &'a str,
+    pub axis: Option<&'a str>,
+    pub unit: Option<String>,
+}
+
+/// Header pattern of monitor quantities
+///
+/// The regular expression must have a `monitor` named capture group and
+/// may have `axis`, `unit` and `quantity` named capture groups.
+/// If there is no `unit` group, the unit is the text within the last parentheses of the header.
+#[derive(Debug, Clone)]
+pub(crate) struct QuantityPattern {
+    quantity: Option<String>,
+    regex: Regex,
+}
+impl QuantityPattern {
+    /// Header pattern of the given quantity
+    pub fn new(quantity: &str, regex: &str) -> Result<Self, MonitorsError> {
+        let regex = Regex::new(regex)?;
+        if !regex.capture_names().any(|name| name == Some("monitor")) {
+            return Err(MonitorsError::Pattern(regex.to_string()));
+        }
+        Ok(Self {
+            quantity: Some(quantity.to_owned()),
+            regex,
+        })
+    }
+    /// Star-CCM+ monitors header pattern
+    pub fn star_ccm() -> Result<Self, MonitorsError> {
+        Ok(Self {
+            quantity: None,
+            regex: Regex::new(
+                r"^(?P<monitor>.+?)(?:_(?P<axis>[XYZ]))? Monitor: (?P<quantity>.+?)(?: \((?P<unit>[^)]*)\))?$",
+            )?,
+        })
+    }
+    pub fn captures<'a>(&self, header: &'a str) -> Option<Capture<'a>> {
+        let capts = self.regex.captures(header)?;
+        let quantity = match (&self.quantity, capts.name("quantity")) {
+            (_, Some(quantity)) => quantity.as_str().to_owned(),
+            (Some(quantity), None) => quantity.clone(),
+            (None, None) => return None,
+        };
+        let unit = capts
+            .name("unit")
+            .map(|unit| unit.as_str().to_owned())
+            .or_else(|| {
+                header
+                    .trim_end()
+                    .strip_suffix(')')
+                    .and_then(|h| h.rsplit_once('('))
+                    .map(|(_, unit)| unit.to_owned())
+            });
+        Some(Capture {
+            quantity,
+            monitor: capts.name("monitor")?.as_str(),
+            axis: capts.name("axis").map(|axis| axis.as_str()),
+            unit,
+        })
+    }
+}
@@ -1,51 +1,26 @@
-use crate::{detrend_mut, MonitorsError, Vector};
-use flate2::read::GzDecoder;
+use super::{
+    cache::{self, CacheKey},
+    catalog::MonitorsCatalog,
+    merge::MergePolicy,
+    parser::{Columns, Patterns},
+    stats::{ExertionStats, Stats, VectorStats},
+    Quantity, Validation,
+};
+use crate::{decompress, detrend_mut, MonitorsError, Vector};
 #[cfg(feature = "plot")]
 use plotters::prelude::*;
 use regex::Regex;
 use std::{
     collections::BTreeMap,
-    fs::File,
     io::Read,
-    ops::{Add, Deref, DerefMut, Div},
-    path::Path,
+    ops::{Add, Div},
+    path::{Path, PathBuf},
     time::Instant,
 };
 use welch_sde::{Build, PowerSpectrum};
 
 type Result<T> = std::result::Result<T, super::MonitorsError>;
 
-pub struct FemNodes(BTreeMap<String, Vector>);
-impl Deref for FemNodes {
-    type Target = BTreeMap<String, Vector>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-impl DerefMut for FemNodes {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-impl Default for FemNodes {
-    fn default() -> Self {
-        let mut fem = FemNodes(BTreeMap::new());
-        fem.insert("M1cov1".to_string(), [0., 13.5620, 5.3064].into());
-        fem.insert("M1cov2".to_string(), [11.7451, 6.7810, 5.3064].into());
-        fem.insert("M1cov3".to_string(), [11.7451, -6.7810, 5.3064].into());
-        fem.insert("M1cov4".to_string(), [0., -13.5621, 5.3064].into());
-        fem.insert("M1cov5".to_string(), [-11.7451, -6.7810, 5.3064].into());
-        fem.insert("M1cov6".to_string(), [-11.7451, 6.7810, 5.3064].into());
-        fem.insert("M1covin1".to_string(), [2.3650, 4.0963, 4.7000].into());
-        fem.insert("M1covin2".to_string(), [4.3000, 0., 4.7000].into());
-        fem.insert("M1covin3".to_string(), [2.3650, -4.0963, 4.7000].into());
-        fem.insert("M1covin4".to_string(), [-2.3650, -4.0963, 4.7000].into());
-        fem.insert("M1covin5".to_string(), [-4.3000, 0., 4.7000].into());
-        fem.insert("M1covin6".to_string(), [-2.3650, 4.0963, 4.7000].into());
-        fem
-    }
-}
 /// A force ['Vector'] and a moment ['Vector']
 #[derive(Default, Debug, Clone)]
 pub struct Exertion {
@@ -55,6 +30,30 @@ pub struct Exertion {
     pub cop: Option<Vector>,
 }
 impl Exertion {
+    /// Force and moment components names, in the order of [Exertion::components]
+    pub const COMPONENTS: [&'static str; 6] = ["Fx", "Fy", "Fz", "Mx", "My", "Mz"];
+    /// Returns the force and moment components `[Fx,Fy,Fz,Mx,My,Mz]`
+    pub fn components(&self) -> [Option<f64>; 6] {
+        [
+            self.force.x,
+            self.force.y,
+            self.force.z,
+            self.moment.x,
+            self.moment.y,
+            self.moment.z,
+        ]
+    }
+    /// Returns mutable references to the force and moment components `[Fx,Fy,Fz,Mx,My,Mz]`
+    pub fn components_mut(&mut self) -> [&mut Option<f64>; 6] {
+        [
+            &mut self.force.x,
+            &mut self.force.y,
+            &mut self.force.z,
+            &mut self.moment.x,
+            &mut self.moment.y,
+            &mut self.moment.z,
+        ]
+    }
     /// Build from a force ['Vector']
     #[allow(dead_code)]
     pub fn from_force(force: Vector) -> Self {
@@ -186,6 +185,12 @@ pub struct MonitorsLoader<const YEAR: u32> {
     time_range: (f64, f64),
     header_regex: String,
     header_exclude_regex: Option<String>,
+    quantities: Vec<(String, String)>,
+    cache: bool,
+    restarts: Vec<PathBuf>,
+    merge_policy: MergePolicy,
+    sampling_rate: Option<f64>,
+    validation: Validation,
 }
 impl<const YEAR: u32> Default for MonitorsLoader<YEAR> {
     fn default() -> Self {
@@ -194,6 +199,12 @@ impl<const YEAR: u32> Default for MonitorsLoader<YEAR> {
             time_range: (0f64, f64::INFINITY),
             header_regex: String::from(r"\w+"),
             header_exclude_regex: None,
+            quantities: Vec::new(),
+            cache: false,
+            restarts: Vec::new(),
+            merge_policy: MergePolicy::default(),
+            sampling_rate: None,
+            validation: Validation::default(),
         }
     }
 }
@@ -232,6 +243,90 @@ impl<const YEAR: u32> MonitorsLoader<YEAR> {
             ..self
         }
     }
+    /// Registers a header pattern for the monitors of a physical quantity
+    ///
+    /// The regular expression must have a `monitor` named capture group and may have `axis` (X, Y or Z)
+    /// and `unit` named capture groups, e.g. `(?P<monitor>\w+)_(?P<axis>[XYZ]) Probe: Velocity`.
+    /// If there is no `unit` group, the unit is the text within the last parentheses of the header.
+    /// The registered patterns take precedence over the default Star-CCM+ pattern
+    /// `<monitor> Monitor: <quantity> (<unit>)`.
+    pub fn quantity<S: Into<String>>(mut self, quantity: S, header_regex: S) -> Self {
+        self.quantities.push((quantity.into(), header_regex.into()));
+        self
+    }
+    /// Adds the monitors of a restarted run of the same CFD case
+    ///
+    /// `data_path` is either the directory of the monitors file or the monitors file itself.
+    /// The monitors are merged in the order the runs are added, starting with the monitors in [MonitorsLoader::data_path]
+    pub fn restart<S: AsRef<Path>>(mut self, data_path: S) -> Self {
+        let path = data_path.as_ref();
+        self.restarts.push(if path.is_dir() {
+            path.join("monitors.csv")
+        } else {
+            path.to_path_buf()
+        });
+        self
+    }
+    /// Sets the policy to merge the overlapping time intervals of restarted runs (default: [MergePolicy::KeepLatest])
+    pub fn merge_policy(self, merge_policy: MergePolicy) -> Self {
+        Self {
+            merge_policy,
+            ..self
+        }
+    }
+    /// Resamples the monitors onto a uniform time grid at the given sampling rate [Hz]
+    pub fn resample(self, sampling_rate: f64) -> Self {
+        Self {
+            sampling_rate: Some(sampling_rate),
+            ..self
+        }
+    }
+    /// Sets the validation policy of the forces and moments components (default: [Validation::Warn])
+    pub fn validation(self, validation: Validation) -> Self {
+        Self { validation, ..self }
+    }
+    /// Merges restarted runs, reports the gaps, validates and resamples the monitors
+    fn repair(&self, mut monitors: Monitors) -> Result<Monitors> {
+        monitors.repair(self.merge_policy);
+        for gap in monitors.gaps(1.5) {
+            log::warn!(
+                "{}: {:.3}s gap in the monitors, from {:.3}s to {:.3}s",
+                self.path,
+                gap.duration(),
+                gap.start,
+                gap.end
+            );
+        }
+        let mut monitors = monitors.validation(self.validation)?;
+        if let Some(rate) = self.sampling_rate {
+            monitors.resample(rate);
+        }
+        Ok(monitors)
+    }
+    /// Selects the columns of the monitors matching the header filters
+    fn select(&self, headers: &[String], patterns: Patterns) -> Result<Columns> {
+        let re_header = Regex::new(&self.header_regex)?;
+        let re_x_header = self
+            .header_exclude_regex
+            .as_deref()
+            .map(Regex::new)
+            .transpose()?;
+        Ok(Columns::new(
+            headers,
+            &patterns.register(&self.quantities)?,
+            &re_header,
+            re_x_header.as_ref(),
+        ))
+    }
+    /// Saves the monitors in a binary cache next to the monitors file and reuses it on later loads
+    ///
+    /// The cache is discarded if the monitors file, the header filters or the time range have changed
+    pub fn cache(self) -> Self {
+        Self {
+            cache: true,
+            ..self
+        }
+    }
 }
 pub trait Is2021or2025 {}
 impl Is2021or2025 for MonitorsLoader<2021> {}
@@ -240,35 +335,17 @@ impl<const Y: u32> MonitorsLoader<Y>
 where
     MonitorsLoader<Y>: Is2021or2025,
 {
-    #[cfg(feature = "bzip2")]
-    fn decompress(&self) -> Result<String> {
-        let mut contents = String::new();
-        let csv_file = File::open(Path::new(&self.path).with_extension("csv.bz2"))?;
-        log::info!("Loading {:?}...", csv_file);
-        let buf = std::io::BufReader::new(csv_file);
-        let mut bz2 = bzip2::bufread::BzDecoder::new(buf);
-        bz2.read_to_string(&mut contents)?;
-        Ok(contents)
+    /// Monitors file, either gzip, bzip2 or zstd compressed or uncompressed
+    fn source(path: &Path) -> Result<PathBuf> {
+        Ok(decompress::find(path, decompress::CSV_EXTENSIONS)?)
     }
-    #[cfg(not(feature = "bzip2"))]
-    fn decompress(&self) -> Result<String> {
-        use crate::MonitorsError;
-
-        let mut contents = String::new();
-        let data_path = Path::new(&self.path).with_extension("csv.z");
+    fn reader(path: &Path) -> Result<Box<dyn Read>> {
+        let data_path = Self::source(path)?;
         log::info!("Loading {:?}...", data_path);
-        let csv_file =
-            File::open(&data_path).map_err(|e| MonitorsError::Io(e, data_path.clone()))?;
-        let mut gz = GzDecoder::new(csv_file);
-        gz.read_to_string(&mut contents)
-            .map_err(|e| MonitorsError::Io(e, data_path))?;
-        Ok(contents)
+        Ok(decompress::open(data_path)?)
     }
-    pub fn load(self) -> Result<Monitors> {
-        let now = Instant::now();
-        let contents = self.decompress()?;
-        let mut rdr = csv::Reader::from_reader(contents.as_bytes());
-
+    /// Selects the monitors columns from the CSV header
+    fn columns<R: Read>(&self, rdr: &mut csv::Reader<R>) -> Result<Columns> {
         let headers: Vec<_> = {
             let headers = rdr.headers()?;
             //headers.iter().take(20).for_each(|h| println!("{}", h));
@@ -280,127 +357,64 @@ where
                 .find(|h| h.contains("M1c_"))
                 .ok_or(MonitorsError::YearMismatch(2021, Y))?;
         }
-
-        let re_htc = Regex::new(
-            r"(\w+) Monitor: Surface Average of Heat Transfer Coefficient \(W/m\^2-K\)",
-        )?;
-        //Cabs_X Monitor 2: Force (N)
-        let re_force = Regex::new(r"(.+)_([XYZ]) Monitor: Force \(N\)")?;
-        let re_moment = Regex::new(r"(.+)Mom_([XYZ]) Monitor: Moment \(N-m\)")?;
-
-        let re_header = Regex::new(&self.header_regex)?;
-        let re_x_header = if let Some(re) = self.header_exclude_regex {
-            Some(Regex::new(&re)?)
+        self.select(&headers, Patterns::star_ccm()?)
+    }
+    /// Returns the catalog of the monitors selected by the header filters
+    ///
+    /// Only the CSV header of the monitors file is read
+    pub fn catalog(&self) -> Result<MonitorsCatalog> {
+        let mut rdr = csv::Reader::from_reader(Self::reader(Path::new(&self.path))?);
+        Ok(self.columns(&mut rdr)?.catalog())
+    }
+    pub fn load(self) -> Result<Monitors> {
+        let mut monitors = self.load_file(Path::new(&self.path))?;
+        for path in &self.restarts {
+            monitors.append(self.load_file(path)?);
+        }
+        self.repair(monitors)
+    }
+    fn load_file(&self, path: &Path) -> Result<Monitors> {
+        let cache_key = if self.cache {
+            let source = Self::source(path)?;
+            CacheKey::new(
+                &source,
+                &self.header_regex,
+                self.header_exclude_regex.as_deref(),
+                &self.quantities,
+                self.time_range,
+            )
+            .map_err(|e| MonitorsError::Io(e, source))
+            .map(Some)?
         } else {
             None
         };
-
-        let mut monitors = Monitors::default();
-
-        for result in rdr.records() {
-            let record = result?;
-            let time = record.iter().next().unwrap().parse::<f64>()?;
-            if time < self.time_range.0 - 1. / 40. || time > self.time_range.1 + 1. / 40. {
-                continue;
-            };
-            monitors.time.push(time);
-            for (data, header) in record.iter().skip(1).zip(headers.iter().skip(1)).filter(
-                |(_, h)| match &re_x_header {
-                    Some(re_x_header) => re_header.is_match(h) && !re_x_header.is_match(h),
-                    None => re_header.is_match(h),
-                },
-            ) {
-                // HTC
-                if let Some(capts) = re_htc.captures(header) {
-                    let key = capts.get(1).unwrap().as_str().to_owned();
-                    let value = data.parse::<f64>()?;
-                    monitors
-                        .heat_transfer_coefficients
-                        .entry(key)
-                        .or_insert_with(Vec::new)
-                        .push(value.abs());
-                }
-                // FORCE
-                if let Some(capts) = re_force.captures(header) {
-                    let key = capts.get(1).unwrap().as_str().to_owned();
-                    let value = data.parse::<f64>()?;
-                    let exertions = monitors
-                        .forces_and_moments
-                        .entry(key)
-                        .or_insert(vec![Exertion::default()]);
-                    let exertion = exertions.last_mut().unwrap();
-                    match capts.get(2).unwrap().as_str() {
-                        "X" => match exertion.force.x {
-                            Some(_) => exertions.push(Exertion::from_force_x(value)),
-                            None => {
-                                exertion.force.x = Some(value);
-                            }
-                        },
-                        "Y" => match exertion.force.y {
-                            Some(_) => exertions.push(Exertion::from_force_y(value)),
-                            None => {
-                                exertion.force.y = Some(value);
-                            }
-                        },
-                        "Z" => match exertion.force.z {
-                            Some(_) => exertions.push(Exertion::from_force_z(value)),
-                            None => {
-                                exertion.force.z = Some(value);
-                            }
-                        },
-                        &_ => (),
-                    };
-                }
-                // MOMENT
-                if let Some(capts) = re_moment.captures(header) {
-                    let key = capts
-                        .get(1)
-                        .unwrap()
-                        .as_str()
-                        .trim_end_matches('_')
-                        .to_owned();
-                    let value = data.parse::<f64>()?;
-                    let exertions = monitors
-                        .forces_and_moments
-                        .entry(key)
-                        .or_insert(vec![Exertion::default()]);
-                    let exertion = exertions.last_mut().unwrap();
-                    match capts.get(2).unwrap().as_str() {
-                        "X" => match exertion.moment.x {
-                            Some(_) => exertions.push(Exertion::from_moment_x(value)),
-                            None => {
-                                exertion.moment.x = Some(value);
-                            }
-                        },
-                        "Y" => match exertion.moment.y {
-                            Some(_) => exertions.push(Exertion::from_moment_y(value)),
-                            None => {
-                                exertion.moment.y = Some(value);
-                            }
-                        },
-                        "Z" => match exertion.moment.z {
-                            Some(_) => exertions.push(Exertion::from_moment_z(value)),
-                            None => {
-                                exertion.moment.z = Some(value);
-                            }
-                        },
-                        &_ => (),
-                    };
-                }
-            }
+        if let Some(monitors) = cache_key.as_ref().and_then(cache::read) {
+            return Ok(monitors);
         }
+        let now = Instant::now();
+        let mut rdr = csv::Reader::from_reader(Self::reader(path)?);
+        let columns = self.columns(&mut rdr)?;
+        let monitors = columns.stream(&mut rdr, self.time_range)?;
         log::info!("... loaded in {:}s", now.elapsed().as_secs());
+        if let Some(key) = cache_key {
+            if let Err(e) = cache::write(&key, &monitors) {
+                log::warn!("failed to cache the monitors to {:?}: {e}", key.path());
+            }
+        }
         Ok(monitors)
     }
 }
 impl MonitorsLoader<2020> {
-    pub fn load(self) -> Result<Monitors> {
-        let csv_file = Path::new(&self.path).with_file_name("monitors-2020.csv");
-        dbg!(&csv_file);
+    fn reader(path: &Path) -> Result<Box<dyn Read>> {
+        let csv_file = decompress::find(
+            path.with_file_name("monitors-2020.csv"),
+            decompress::CSV_EXTENSIONS,
+        )?;
         log::info!("Loading {:?}...", csv_file);
-        let now = Instant::now();
-        let mut rdr = csv::Reader::from_path(csv_file)?;
-
+        Ok(decompress::open(csv_file)?)
+    }
+    /// Selects the monitors columns from the CSV header
+    fn columns<R: Read>(&self, rdr: &mut csv::Reader<R>) -> Result<Columns> {
         let headers: Vec<_> = {
             let headers = rdr.headers()?;
             headers
@@ -408,116 +422,45 @@ impl MonitorsLoader<2020> {
                 .map(|x| x.split_whitespace().collect::<Vec<&str>>().join(""))
                 .collect()
         };
-
-        let re_force = Regex::new(r"Force(\w+)([xyz])Monitor:Force\(N\)").unwrap();
-        let re_moment = Regex::new(r"Moment(\w+)([xyz])Monitor:Moment\(N-m\)").unwrap();
-
-        let re_header = Regex::new(&self.header_regex)?;
-        let re_x_header = if let Some(re) = self.header_exclude_regex {
-            Some(Regex::new(&re)?)
-        } else {
-            None
-        };
-
-        let mut monitors = Monitors::default();
-
-        for result in rdr.records() {
-            let record = result?;
-            let time = record.iter().next().unwrap().parse::<f64>()?;
-            if time < self.time_range.0 - 1. / 40. || time > self.time_range.1 + 1. / 40. {
-                continue;
-            };
-            monitors.time.push(time);
-            for (data, header) in record.iter().skip(1).zip(headers.iter().skip(1)).filter(
-                |(_, h)| match &re_x_header {
-                    Some(re_x_header) => re_header.is_match(h) && !re_x_header.is_match(h),
-                    None => re_header.is_match(h),
-                },
-            ) {
-                // FORCE
-                if let Some(capts) = re_force.captures(header) {
-                    let key = capts.get(1).unwrap().as_str().to_owned();
-                    let value = data.parse::<f64>()?;
-                    let exertions = monitors
-                        .forces_and_moments
-                        .entry(key)
-                        .or_insert(vec![Exertion::default()]);
-                    let exertion = exertions.last_mut().unwrap();
-                    match capts.get(2).unwrap().as_str() {
-                        "x" => match exertion.force.x {
-                            Some(_) => exertions.push(Exertion::from_force_x(value)),
-                            None => {
-                                exertion.force.x = Some(value);
-                            }
-                        },
-                        "y" => match exertion.force.y {
-                            Some(_) => exertions.push(Exertion::from_force_y(value)),
-                            None => {
-                                exertion.force.y = Some(value);
-                            }
-                        },
-                        "z" => match exertion.force.z {
-                            Some(_) => exertions.push(Exertion::from_force_z(value)),
-                            None => {
-                                exertion.force.z = Some(value);
-                            }
-                        },
-                        &_ => (),
-                    };
-                }
-                // MOMENT
-                if let Some(capts) = re_moment.captures(header) {
-                    let key = capts
-                        .get(1)
-                        .unwrap()
-                        .as_str()
-                        .trim_end_matches('_')
-                        .to_owned();
-                    let value = data.parse::<f64>()?;
-                    let exertions = monitors
-                        .forces_and_moments
-                        .entry(key)
-                        .or_insert(vec![Exertion::default()]);
-                    let exertion = exertions.last_mut().unwrap();
-                    match capts.get(2).unwrap().as_str() {
-                        "x" => match exertion.moment.x {
-                            Some(_) => exertions.push(Exertion::from_moment_x(value)),
-                            None => {
-                                exertion.moment.x = Some(value);
-                            }
-                        },
-                        "y" => match exertion.moment.y {
-                            Some(_) => exertions.push(Exertion::from_moment_y(value)),
-                            None => {
-                                exertion.moment.y = Some(value);
-                            }
-                        },
-                        "z" => match exertion.moment.z {
-                            Some(_) => exertions.push(Exertion::from_moment_z(value)),
-                            None => {
-                                exertion.moment.z = Some(value);
-                            }
-                        },
-                        &_ => (),
-                    };
-                }
-            }
+        self.select(&headers, Patterns::b2020()?)
+    }
+    /// Returns the catalog of the monitors selected by the header filters
+    ///
+    /// Only the CSV header of the monitors file is read
+    pub fn catalog(&self) -> Result<MonitorsCatalog> {
+        let mut rdr = csv::Reader::from_reader(Self::reader(Path::new(&self.path))?);
+        Ok(self.columns(&mut rdr)?.catalog())
+    }
+    pub fn load(self) -> Result<Monitors> {
+        let mut monitors = self.load_file(Path::new(&self.path))?;
+        for path in &self.restarts {
+            monitors.append(self.load_file(path)?);
         }
+        self.repair(monitors)
+    }
+    fn load_file(&self, path: &Path) -> Result<Monitors> {
+        let now = Instant::now();
+        let mut rdr = csv::Reader::from_reader(Self::reader(path)?);
+        let columns = self.columns(&mut rdr)?;
+        let monitors = columns.stream(&mut rdr, self.time_range)?;
         log::info!("... loaded in {:}s", now.elapsed().as_secs());
         Ok(monitors)
     }
 }
 
 /// Gather all the monitors of a CFD run
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Monitors {
     pub time: Vec<f64>,
     pub heat_transfer_coefficients: BTreeMap<String, Vec<f64>>,
     pub forces_and_moments: BTreeMap<String, Vec<Exertion>>,
+    /// Other monitors, per physical quantity
+    pub quantities: BTreeMap<String, Quantity>,
     pub total_forces_and_moments: Vec<Exertion>,
     //    pub segments_integrated_forces: Option<Vec<Mirror>>,
+    /// Time step of the `dosio` outputs
+    #[cfg(feature = "dosio")]
     time_idx: usize,
-    data: Option<Vec<f64>>,
 }
 impl Monitors {
     pub fn loader<S, const YEAR: u32>(data_path: S) -> MonitorsLoader<YEAR>
@@ -552,291 +495,125 @@ impl Monitors {
         }
         self
     }
-    /// Keeps only the last `period` seconds of the monitors
+    /// Keeps only the last `period` seconds of the monitors, see [Monitors::last]
     pub fn keep_last(&mut self, period: usize) -> &mut Self {
-        let n_sample = 1 + period * crate::FORCE_SAMPLING_FREQUENCY as usize;
-        let i = if self.len() > n_sample {
-            self.len() - n_sample
-        } else {
-            0
-        };
-        let _: Vec<_> = self.time.drain(..i).collect();
-        for value in self.heat_transfer_coefficients.values_mut() {
-            let _: Vec<_> = value.drain(..i).collect();
-        }
-        for value in self.forces_and_moments.values_mut() {
-            let _: Vec<_> = value.drain(..i).collect();
-        }
-        if i < self.total_forces_and_moments.len() {
-            let _: Vec<_> = self.total_forces_and_moments.drain(..i).collect();
-        }
-        self
-    }
-    pub fn into_local(&mut self) -> &mut Self {
-        let nodes = FemNodes::default();
-        for (key, value) in self.forces_and_moments.iter_mut() {
-            if let Some(node) = nodes.get(key) {
-                value.iter_mut().for_each(|v| {
-                    (*v).into_local(node.clone());
-                });
-            }
-        }
+        *self = self.last(period as f64);
         self
     }
     /// Return a latex table with HTC monitors summary
     pub fn htc_latex_table(&self, stats_duration: f64) -> Option<String> {
-        let max_value = |x: &[f64]| x.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max);
-        let min_value = |x: &[f64]| x.iter().cloned().fold(std::f64::INFINITY, f64::min);
-        let minmax = |x: &[f64]| (min_value(x), max_value(x));
-        let stats = |x: &[f64]| {
-            let n = x.len() as f64;
-            let mean = x.iter().sum::<f64>() / n;
-            let std = (x.iter().map(|x| x - mean).fold(0f64, |s, x| s + x * x) / n).sqrt();
-            (mean, std)
-        };
         if self.heat_transfer_coefficients.is_empty() {
-            None
-        } else {
-            let duration = self.time.last().unwrap();
-            let time_filter: Vec<_> = self
-                .time
-                .iter()
-                .map(|t| t - duration + stats_duration >= 0f64)
-                .collect();
-            let data: Vec<_> = self
-                .heat_transfer_coefficients
-                .iter()
-                .map(|(key, value)| {
-                    let time_filtered_value: Vec<_> = value
-                        .iter()
-                        .zip(time_filter.iter())
-                        .filter(|(_, t)| **t)
-                        .map(|(v, _)| *v)
-                        .collect();
-                    let (mean, std) = stats(&time_filtered_value);
-                    let (min, max) = minmax(&time_filtered_value);
-                    format!(
-                        " {:} & {:.3} & {:.3} & {:.3} & {:.3} \\\\",
-                        key, mean, std, min, max
-                    )
-                })
-                .collect();
-            Some(data.join("\n"))
+            return None;
         }
+        let data: Vec<_> = self
+            .stats(stats_duration)
+            .heat_transfer_coefficients
+            .iter()
+            .map(|(key, stats)| match stats {
+                Some(Stats {
+                    mean,
+                    std,
+                    min,
+                    max,
+                    ..
+                }) => format!(
+                    " {:} & {:.3} & {:.3} & {:.3} & {:.3} \\\\",
+                    key, mean, std, min, max
+                ),
+                None => format!(" {:} \\\\", key),
+            })
+            .collect();
+        Some(data.join("\n"))
     }
-    /// Return a latex table with force monitors summary
-    pub fn force_latex_table(&self, stats_duration: f64) -> Option<String> {
-        let max_value = |x: &[Vector]| {
-            x.iter()
-                .filter_map(|x| x.magnitude())
-                .fold(std::f64::NEG_INFINITY, f64::max)
-        };
-        let min_value = |x: &[Vector]| {
-            x.iter()
-                .filter_map(|x| x.magnitude())
-                .fold(std::f64::INFINITY, f64::min)
-        };
-        let minmax = |x: &[Vector]| (min_value(x), max_value(x));
-        let stats = |x: &[Vector]| {
-            let n = x.len() as f64;
-            if let Some(mean) = x.iter().fold(Vector::zero(), |s, x| s + x) / n {
-                let std = (x
-                    .iter()
-                    .filter_map(|x| x - mean.clone())
-                    .filter_map(|x| x.norm_squared())
-                    .sum::<f64>()
-                    / n)
-                    .sqrt();
-                Some((mean.magnitude(), std))
-            } else {
-                None
-            }
-        };
+    fn vector_latex_table<F>(&self, stats_duration: f64, vector: F) -> Option<String>
+    where
+        F: Fn(&ExertionStats) -> &VectorStats,
+    {
         if self.forces_and_moments.is_empty() {
-            None
-        } else {
-            let duration = self.time.last().unwrap();
-            let time_filter: Vec<_> = self
-                .time
-                .iter()
-                .map(|t| t - duration + stats_duration - crate::FORCE_SAMPLING > 0f64)
-                .collect();
-            let data: Vec<_> = self
-                .forces_and_moments
-                .iter()
-                .map(|(key, value)| {
-                    let force: Vec<Vector> = value
-                        .iter()
-                        .zip(time_filter.iter())
-                        .filter(|(_, t)| **t)
-                        .map(|(e, _)| e.force.clone())
-                        .collect();
-                    match stats(&force) {
-                        Some((Some(mean), std)) => {
-                            let (min, max) = minmax(&force);
-                            format!(
-                                " {:} & {:.3} & {:.3} & {:.3} & {:.3} \\\\",
-                                key.replace("_", " "),
-                                mean,
-                                std,
-                                min,
-                                max
-                            )
-                        }
-                        _ => format!(" {:} \\\\", key.replace("_", " ")),
-                    }
-                })
-                .collect();
-            Some(data.join("\n"))
+            return None;
         }
+        let data: Vec<_> = self
+            .stats(stats_duration)
+            .forces_and_moments
+            .iter()
+            .map(|(key, stats)| {
+                let stats = vector(stats);
+                match (
+                    stats.mean_magnitude(),
+                    stats.std_magnitude(),
+                    stats.magnitude,
+                ) {
+                    (Some(mean), Some(std), Some(Stats { min, max, .. })) => format!(
+                        " {:} & {:.3} & {:.3} & {:.3} & {:.3} \\\\",
+                        key.replace("_", " "),
+                        mean,
+                        std,
+                        min,
+                        max
+                    ),
+                    _ => format!(" {:} \\\\", key.replace("_", " ")),
+                }
+            })
+            .collect();
+        Some(data.join("\n"))
+    }
+    /// Return a latex table with force monitors summary
+    pub fn force_latex_table(&self, stats_duration: f64) -> Option<String> {
+        self.vector_latex_table(stats_duration, |stats| &stats.force)
     }
     /// Return a latex table with moment monitors summary
     pub fn moment_latex_table(&self, stats_duration: f64) -> Option<String> {
-        let max_value = |x: &[Vector]| {
-            x.iter()
-                .filter_map(|x| x.magnitude())
-                .fold(std::f64::NEG_INFINITY, f64::max)
-        };
-        let min_value = |x: &[Vector]| {
-            x.iter()
-                .filter_map(|x| x.magnitude())
-                .fold(std::f64::INFINITY, f64::min)
-        };
-        let minmax = |x: &[Vector]| (min_value(x), max_value(x));
-        let stats = |x: &[Vector]| {
-            let n = x.len() as f64;
-            if let Some(mean) = x.iter().fold(Vector::zero(), |s, x| s + x) / n {
-                let std = (x
-                    .iter()
-                    .filter_map(|x| x - mean.clone())
-                    .filter_map(|x| x.norm_squared())
-                    .sum::<f64>()
-                    / n)
-                    .sqrt();
-                Some((mean.magnitude(), std))
-            } else {
-                None
-            }
-        };
-        if self.forces_and_moments.is_empty() {
-            None
-        } else {
-            let duration = self.time.last().unwrap();
-            let time_filter: Vec<_> = self
-                .time
-                .iter()
-                .map(|t| t - duration + stats_duration - crate::FORCE_SAMPLING > 0f64)
-                .collect();
-            let data: Vec<_> = self
-                .forces_and_moments
-                .iter()
-                .map(|(key, value)| {
-                    let moment: Vec<Vector> = value
-                        .iter()
-                        .zip(time_filter.iter())
-                        .filter(|(_, t)| **t)
-                        .map(|(e, _)| e.moment.clone())
-                        .collect();
-                    match stats(&moment) {
-                        Some((Some(mean), std)) => {
-                            let (min, max) = minmax(&moment);
-                            format!(
-                                " {:} & {:.3} & {:.3} & {:.3} & {:.3} \\\\",
-                                key.replace("_", " "),
-                                mean,
-                                std,
-                                min,
-                                max
-                            )
-                        }
-                        _ => format!(" {:} \\\\", key.replace("_", " ")),
-                    }
-                })
-                .collect();
-            Some(data.join("\n"))
-        }
+        self.vector_latex_table(stats_duration, |stats| &stats.moment)
     }
     /// Print out a monitors summary
     pub fn summary(&mut self) {
-        let max_value = |x: &[f64]| x.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max);
-        let min_value = |x: &[f64]| x.iter().cloned().fold(std::f64::INFINITY, f64::min);
-        let minmax = |x| (min_value(x), max_value(x));
-        let stats = |x: &[f64]| {
-            let n = x.len() as f64;
-            let mean = x.iter().sum::<f64>() / n;
-            let std = (x.iter().map(|x| x - mean).fold(0f64, |s, x| s + x * x) / n).sqrt();
-            (mean, std)
-        };
+        let stats = self.stats(f64::INFINITY);
         println!("SUMMARY:");
-        println!(" - # of records: {}", self.len());
+        println!(" - # of records: {}", stats.samples);
         println!(
             " - time range: [{:8.3}-{:8.3}]s",
-            self.time[0],
-            self.time.last().unwrap()
+            stats.time_range.0, stats.time_range.1
         );
-        let n_htc = self.heat_transfer_coefficients.len();
-        if !self.heat_transfer_coefficients.is_empty() {
+        let n_htc = stats.heat_transfer_coefficients.len();
+        if n_htc > 0 {
             println!(" - # of HTC elements: {}", n_htc);
             println!(" - HTC [W/m^2-K]:");
             println!(
                 "    {:^16}: ({:^12}, {:^12})  ({:^12}, {:^12})",
                 "ELEMENT", "MEAN", "STD", "MIN", "MAX"
             );
-            self.heat_transfer_coefficients
+            stats
+                .heat_transfer_coefficients
                 .iter()
-                .for_each(|(key, value)| {
-                    println!(
+                .for_each(|(key, stats)| match stats {
+                    Some(s) => println!(
                         "  - {:16}: {:>12.3?}  {:>12.3?}",
                         key,
-                        stats(value),
-                        minmax(value)
-                    );
+                        (s.mean, s.std),
+                        (s.min, s.max)
+                    ),
+                    None => println!("  - {:16}: {:?}", key, None::<f64>),
                 });
         }
-        let n_fm = self.forces_and_moments.len();
-        if !self.forces_and_moments.is_empty() {
+        let n_fm = stats.forces_and_moments.len();
+        if n_fm > 0 {
             println!(" - # of elements with forces & moments: {}", n_fm);
-            let (total_force, total_moment): (Vec<_>, Vec<_>) =
-                self.forces_and_moments.values().fold(
-                    (
-                        vec![Vector::zero(); self.len()],
-                        vec![Vector::zero(); self.len()],
-                    ),
-                    |(mut fa, mut ma), value| {
-                        fa.iter_mut()
-                            .zip(value.iter())
-                            .for_each(|(mut fa, e)| fa += &e.force);
-                        ma.iter_mut()
-                            .zip(value.iter())
-                            .for_each(|(mut ma, e)| ma += &e.moment);
-                        (fa, ma)
-                    },
-                );
-            let total_force: Vec<Vector> = total_force.iter().map(|x| x.clone()).collect();
-            let total_moment: Vec<Vector> = total_moment.iter().map(|x| x.clone()).collect();
+            let total = stats.total.clone().unwrap_or_default();
             println!(" - Forces magnitude [N]:");
             println!("    {:^16}: [{:^12}]   [{:^12}]", "ELEMENT", "MEAN", "STD");
-            self.forces_and_moments.iter().for_each(|(key, value)| {
-                let force: Vec<Vector> = value.iter().map(|e| e.force.clone()).collect();
-                Self::display(key, &force);
-            });
-            Self::display("TOTAL", &total_force);
+            stats
+                .forces_and_moments
+                .iter()
+                .for_each(|(key, stats)| Self::display_stats(key, &stats.force));
+            Self::display_stats("TOTAL", &total.force);
             println!(" - Moments magnitude [N-m]:");
             println!("    {:^16}: [{:^12}]   [{:^12}]", "ELEMENT", "MEAN", "STD");
-            self.forces_and_moments.iter().for_each(|(key, value)| {
-                let moment: Vec<Vector> = value.iter().map(|e| e.moment.clone()).collect();
-                Self::display(key, &moment);
-            });
-            Self::display("TOTAL", &total_moment);
-            self.total_forces_and_moments = total_force
-                .into_iter()
-                .zip(total_moment.into_iter())
-                .map(|(force, moment)| Exertion {
-                    force,
-                    moment,
-                    cop: None,
-                })
-                .collect();
+            stats
+                .forces_and_moments
+                .iter()
+                .for_each(|(key, stats)| Self::display_stats(key, &stats.moment));
+            Self::display_stats("TOTAL", &total.moment);
+            self.total_exertion();
         }
     }
     pub fn total_exertion(&mut self) -> &mut Self {
@@ -866,41 +643,12 @@ impl Monitors {
             .collect();
         self
     }
+    /// Print out the mean and the standard deviation of the vector components
     pub fn display(key: &str, data: &[Vector]) {
-        /*
-            let max_value = |x: &[f64]| x.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max);
-            let min_value = |x: &[f64]| x.iter().cloned().fold(std::f64::INFINITY, f64::min);
-            let stats = |x: &[f64]| {
-                let n = x.len() as f64;
-                let mean = x.iter().sum::<f64>() / n;
-                let std = (x.iter().map(|x| x - mean).fold(0f64, |s, x| s + x * x) / n).sqrt();
-                (mean, std)
-            };
-        */
-        let stats = |x: &[Vector]| -> Option<(Vec<f64>, Vec<f64>)> {
-            let n = x.len() as f64;
-            if let Some(mean) = x.iter().fold(Vector::zero(), |s, x| s + x) / n {
-                let std = x
-                    .iter()
-                    .filter_map(|x| x - mean.clone())
-                    .filter_map(|x| -> Option<Vec<f64>> { x.into() })
-                    .fold(vec![0f64; 3], |mut a, x| {
-                        a.iter_mut().zip(x.iter()).for_each(|(a, &x)| *a += x * x);
-                        a
-                    });
-                let mean: Option<Vec<f64>> = mean.into();
-                Some((
-                    mean.unwrap(),
-                    std.iter()
-                        .map(|x| (*x / n as f64).sqrt())
-                        .collect::<Vec<_>>(),
-                ))
-            } else {
-                None
-            }
-        };
-
-        match stats(data) {
+        Self::display_stats(key, &VectorStats::new(data));
+    }
+    fn display_stats(key: &str, stats: &VectorStats) {
+        match stats.mean().zip(stats.std()) {
             Some((mean, std)) => {
                 println!("  - {:16}: {:>12.3?}  {:>12.3?}", key, mean, std);
             }
@@ -951,38 +699,6 @@ impl Monitors {
             })
             .collect::<std::result::Result<Vec<()>, csv::Error>>()?)
     }
-    #[cfg(feature = "windloading")]
-    pub fn m1covers_windloads(&self) -> Result<(), Box<dyn std::error::Error>> {
-        use windloading::{Loads, WindLoads};
-        let keys = vec![
-            "M1cov1", "M1cov6", "M1cov5", "M1cov4", "M1cov3", "M1cov2", "M1covin2", "M1covin1",
-            "M1covin6", "M1covin5", "M1covin4", "M1covin3",
-        ];
-        let mut loads: Vec<Vec<f64>> = Vec::with_capacity(72 * self.len());
-        for k in 0..self.len() {
-            let mut fm: Vec<f64> = Vec::with_capacity(72);
-            for &key in &keys {
-                let exrt = self.forces_and_moments.get(key).unwrap().get(k).unwrap();
-                fm.append(
-                    &mut exrt
-                        .force
-                        .as_array()
-                        .iter()
-                        .cloned()
-                        .chain(exrt.moment.as_array().iter().cloned())
-                        .cloned()
-                        .collect::<Vec<f64>>(),
-                );
-            }
-            loads.push(fm);
-        }
-        let mut windloads: WindLoads = Default::default();
-        windloads.time = self.time.clone();
-        windloads.loads = vec![Some(Loads::OSSMirrorCovers6F(loads))];
-        let mut file = std::fs::File::create("windloads.pkl")?;
-        serde_pickle::to_writer(&mut file, &windloads, Default::default())?;
-        Ok(())
-    }
     #[cfg(feature = "plot")]
     pub fn plot_htc(&self) {
         if self.heat_transfer_coefficients.is_empty() {
@@ -1300,35 +1016,17 @@ impl Monitors {
             .unwrap();
     }
 }
-impl Iterator for Monitors {
-    type Item = ();
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let i = self.time_idx;
-        self.time_idx += 1;
-        self.data = Some(Vec::new());
-        for e in self.forces_and_moments.values() {
-            if let (Some(mut f), Some(mut m)) = ((&e[i].force).into(), (&e[i].moment).into()) {
-                if let Some(x) = self.data.as_mut() {
-                    x.append(&mut f);
-                    x.append(&mut m);
-                }
-            } else {
-                return None;
-            }
-        }
-        Some(())
-    }
-}
-
 #[cfg(feature = "dosio")]
 pub mod dos {
     use dosio::{ios, DOSIOSError, Dos, IO};
     impl Dos for super::Monitors {
         type Input = ();
         type Output = Vec<f64>;
+        /// Returns the flattened snapshot of the next time step, see [Monitors::flat_snapshot](super::Monitors::flat_snapshot)
         fn outputs(&mut self) -> Option<Vec<IO<Self::Output>>> {
-            Some(vec![ios!(CFD2021106F(self.data.as_ref().unwrap().clone()))])
+            let (_, data) = self.flat_snapshot(self.time_idx)?;
+            self.time_idx += 1;
+            Some(vec![ios!(CFD2021106F(data))])
         }
         fn inputs(
             &mut self,
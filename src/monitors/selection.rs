@@ -0,0 +1,186 @@
+//! Post-load selection and time slicing
+//!
+//! The methods return owned copies of the [Monitors], leaving the original untouched.
+//! The total forces and moments are kept only if they were already computed,
+//! and are then updated to the selected monitors.
+
+use super::{Monitors, MonitorsError, Quantity, Series};
+use regex::Regex;
+use std::ops::Range;
+
+type Result<T> = std::result::Result<T, MonitorsError>;
+
+/// Clamps `range` to a time series of length `n`
+fn clamp(range: &Range<usize>, n: usize) -> Range<usize> {
+    range.start.min(n)..range.end.min(n)
+}
+
+impl Series {
+    /// Returns a copy of the samples within `range`
+    pub(crate) fn slice(&self, range: Range<usize>) -> Self {
+        match self {
+            Series::Scalar(values) => Series::Scalar(values[clamp(&range, values.len())].to_vec()),
+            Series::Vector(values) => Series::Vector(values[clamp(&range, values.len())].to_vec()),
+        }
+    }
+}
+
+impl Monitors {
+    /// Returns the index of the first sample of the last `seconds` of the monitors
+    ///
+    /// The first sample is within half a sampling period of `seconds` before the last sample
+    pub(crate) fn last_index(&self, seconds: f64) -> usize {
+        let Some(&end) = self.time.last() else {
+            return 0;
+        };
+        let half_period = self
+            .time
+            .windows(2)
+            .last()
+            .map_or(0f64, |t| 0.5 * (t[1] - t[0]));
+        self.time
+            .partition_point(|&t| t < end - seconds - half_period)
+    }
+    /// Returns a copy of the monitors whose name satisfies the predicate
+    fn filter<F: Fn(&str) -> bool>(&self, predicate: F) -> Self {
+        let mut monitors = Monitors::default();
+        monitors.time = self.time.clone();
+        monitors.heat_transfer_coefficients = self
+            .heat_transfer_coefficients
+            .iter()
+            .filter(|(key, _)| predicate(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        monitors.forces_and_moments = self
+            .forces_and_moments
+            .iter()
+            .filter(|(key, _)| predicate(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        monitors.quantities = self
+            .quantities
+            .iter()
+            .filter_map(|(quantity, q)| {
+                let q = Quantity {
+                    unit: q.unit.clone(),
+                    monitors: q
+                        .monitors
+                        .iter()
+                        .filter(|(key, _)| predicate(key))
+                        .map(|(key, series)| (key.clone(), series.clone()))
+                        .collect(),
+                };
+                (!q.monitors.is_empty()).then(|| (quantity.clone(), q))
+            })
+            .collect();
+        if !self.total_forces_and_moments.is_empty() {
+            monitors.total_exertion();
+        }
+        monitors
+    }
+    /// Returns a copy of the monitors whose name matches the regular expression
+    pub fn select(&self, regex: &str) -> Result<Self> {
+        let re = Regex::new(regex)?;
+        Ok(self.filter(|key| re.is_match(key)))
+    }
+    /// Returns a copy of the monitors whose name does not match the regular expression
+    pub fn exclude(&self, regex: &str) -> Result<Self> {
+        let re = Regex::new(regex)?;
+        Ok(self.filter(|key| !re.is_match(key)))
+    }
+    /// Returns a copy of the samples within `range`
+    ///
+    /// Shorter time series are truncated to `range`
+    fn slice(&self, range: Range<usize>) -> Self {
+        let mut monitors = Monitors::default();
+        monitors.time = self.time[range.clone()].to_vec();
+        monitors.heat_transfer_coefficients = self
+            .heat_transfer_coefficients
+            .iter()
+            .map(|(key, value)| (key.clone(), value[clamp(&range, value.len())].to_vec()))
+            .collect();
+        monitors.forces_and_moments = self
+            .forces_and_moments
+            .iter()
+            .map(|(key, value)| (key.clone(), value[clamp(&range, value.len())].to_vec()))
+            .collect();
+        monitors.quantities = self
+            .quantities
+            .iter()
+            .map(|(quantity, q)| {
+                (
+                    quantity.clone(),
+                    Quantity {
+                        unit: q.unit.clone(),
+                        monitors: q
+                            .monitors
+                            .iter()
+                            .map(|(key, series)| (key.clone(), series.slice(range.clone())))
+                            .collect(),
+                    },
+                )
+            })
+            .collect();
+        if !self.total_forces_and_moments.is_empty() {
+            monitors.total_forces_and_moments = self.total_forces_and_moments
+                [clamp(&range, self.total_forces_and_moments.len())]
+            .to_vec();
+        }
+        monitors
+    }
+    /// Returns a copy of the samples with time within `[t0, t1]`
+    pub fn time_window(&self, t0: f64, t1: f64) -> Self {
+        let i = self.time.partition_point(|&t| t < t0);
+        let j = self.time.partition_point(|&t| t <= t1).max(i);
+        self.slice(i..j)
+    }
+    /// Returns a copy of the last `seconds` of the monitors
+    pub fn last(&self, seconds: f64) -> Self {
+        self.slice(self.last_index(seconds)..self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitors::Exertion;
+
+    #[test]
+    fn selection() {
+        let mut monitors = Monitors::default();
+        monitors.time = (0..11).map(|i| 100. + i as f64 * 0.05).collect();
+        for part in ["Cring_1", "Cring_2", "GIR"] {
+            monitors.forces_and_moments.insert(
+                part.into(),
+                (0..11)
+                    .map(|i| Exertion::from(([0.; 3], ([i as f64; 3], [0.; 3]))))
+                    .collect(),
+            );
+            monitors
+                .heat_transfer_coefficients
+                .insert(part.into(), (0..11).map(|i| i as f64).collect());
+        }
+        monitors.total_exertion();
+
+        let crings = monitors.select("^Cring").unwrap();
+        assert_eq!(
+            crings.forces_and_moments.keys().collect::<Vec<_>>(),
+            vec!["Cring_1", "Cring_2"]
+        );
+        assert_eq!(crings.total_forces_and_moments[10].force.x, Some(20.));
+        assert_eq!(monitors.total_forces_and_moments[10].force.x, Some(30.));
+        let gir = monitors.exclude("Cring").unwrap();
+        assert_eq!(gir.heat_transfer_coefficients.len(), 1);
+
+        let window = monitors.time_window(100.09, 100.21);
+        assert_eq!(window.len(), 3);
+        assert_eq!(window.heat_transfer_coefficients["GIR"], vec![2., 3., 4.]);
+        assert_eq!(window.total_forces_and_moments.len(), 3);
+
+        let last = monitors.last(0.25);
+        assert_eq!(last.len(), 6);
+        assert_eq!(last.forces_and_moments["GIR"][0].force.x, Some(5.));
+        assert_eq!(monitors.len(), 11);
+        assert!(monitors.time_window(200., 300.).is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+//! Time step snapshots
+//!
+//! A snapshot is the forces and moments of all the monitors at one time step,
+//! either keyed by monitor name, see [Snapshot], or flattened into a vector, see [Monitors::flat_snapshot].
+//!
+//! The snapshots are iterated by borrowing with [Monitors::snapshots] or `for (time, snapshot) in &monitors`,
+//! by consuming with `for (time, snapshot) in monitors`, or in parallel with [Monitors::par_snapshots]
+//! and [Monitors::par_flat_snapshots].
+//!
+//! ```no_run
+//! use parse_monitors::MonitorsLoader;
+//! use rayon::prelude::*;
+//!
+//! let monitors = MonitorsLoader::<2021>::default().load().unwrap();
+//! for (time, snapshot) in &monitors {
+//!     println!("{time}: {:?}", snapshot["M1cell"].force);
+//! }
+//! let max_fz = monitors
+//!     .par_flat_snapshots()
+//!     .map(|(_, fm)| fm.chunks(6).map(|fm| fm[2]).sum::<f64>())
+//!     .reduce(|| f64::NEG_INFINITY, f64::max);
+//! ```
+
+use super::{Exertion, Monitors};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+
+/// Forces and moments at one time step keyed by monitor name
+pub type Snapshot<'a> = BTreeMap<&'a str, &'a Exertion>;
+
+/// Flattens the force and the moment, missing components are NaN
+fn flatten(e: &Exertion) -> [f64; 6] {
+    e.components().map(|x| x.unwrap_or(f64::NAN))
+}
+
+impl Monitors {
+    /// Returns the time and the snapshot at time step `i`
+    ///
+    /// The monitors with less than `i+1` samples are left out of the snapshot
+    pub fn snapshot(&self, i: usize) -> Option<(f64, Snapshot<'_>)> {
+        let &time = self.time.get(i)?;
+        Some((
+            time,
+            self.forces_and_moments
+                .iter()
+                .filter_map(|(key, value)| value.get(i).map(|e| (key.as_str(), e)))
+                .collect(),
+        ))
+    }
+    /// Returns the time and the flattened snapshot at time step `i`
+    ///
+    /// The snapshot is `[Fx,Fy,Fz,Mx,My,Mz]` of each monitor in the alphabetical order of the monitor names
+    /// i.e. the order of [Monitors::forces_and_moments],
+    /// missing components and monitors with less than `i+1` samples are NaN
+    pub fn flat_snapshot(&self, i: usize) -> Option<(f64, Vec<f64>)> {
+        let &time = self.time.get(i)?;
+        Some((
+            time,
+            self.forces_and_moments
+                .values()
+                .flat_map(|value| value.get(i).map_or([f64::NAN; 6], flatten))
+                .collect(),
+        ))
+    }
+    /// Returns an iterator over the time steps snapshots
+    pub fn snapshots(&self) -> Snapshots<'_> {
+        Snapshots {
+            monitors: self,
+            range: 0..self.len(),
+        }
+    }
+    /// Returns an iterator over the time steps flattened snapshots, see [Monitors::flat_snapshot]
+    pub fn flat_snapshots(&self) -> impl ExactSizeIterator<Item = (f64, Vec<f64>)> + '_ {
+        (0..self.len()).map(|i| self.flat_snapshot(i).expect("time step out of range"))
+    }
+    /// Returns a parallel iterator over the time steps snapshots
+    pub fn par_snapshots(&self) -> impl IndexedParallelIterator<Item = (f64, Snapshot<'_>)> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .map(|i| self.snapshot(i).expect("time step out of range"))
+    }
+    /// Returns a parallel iterator over the time steps flattened snapshots, see [Monitors::flat_snapshot]
+    pub fn par_flat_snapshots(&self) -> impl IndexedParallelIterator<Item = (f64, Vec<f64>)> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .map(|i| self.flat_snapshot(i).expect("time step out of range"))
+    }
+}
+
+/// Borrowing iterator over the [Monitors] snapshots
+pub struct Snapshots<'a> {
+    monitors: &'a Monitors,
+    range: std::ops::Range<usize>,
+}
+impl<'a> Iterator for Snapshots<'a> {
+    type Item = (f64, Snapshot<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().and_then(|i| self.monitors.snapshot(i))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+impl DoubleEndedIterator for Snapshots<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .and_then(|i| self.monitors.snapshot(i))
+    }
+}
+impl ExactSizeIterator for Snapshots<'_> {}
+impl<'a> IntoIterator for &'a Monitors {
+    type Item = (f64, Snapshot<'a>);
+    type IntoIter = Snapshots<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.snapshots()
+    }
+}
+
+/// Consuming iterator over the [Monitors] snapshots
+///
+/// The monitors that run out of samples are left out of the snapshots
+pub struct IntoSnapshots {
+    time: std::vec::IntoIter<f64>,
+    forces_and_moments: BTreeMap<String, std::vec::IntoIter<Exertion>>,
+}
+impl Iterator for IntoSnapshots {
+    type Item = (f64, BTreeMap<String, Exertion>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let time = self.time.next()?;
+        Some((
+            time,
+            self.forces_and_moments
+                .iter_mut()
+                .filter_map(|(key, value)| value.next().map(|e| (key.clone(), e)))
+                .collect(),
+        ))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.time.size_hint()
+    }
+}
+impl ExactSizeIterator for IntoSnapshots {}
+impl IntoIterator for Monitors {
+    type Item = (f64, BTreeMap<String, Exertion>);
+    type IntoIter = IntoSnapshots;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoSnapshots {
+            time: self.time.into_iter(),
+            forces_and_moments: self
+                .forces_and_moments
+                .into_iter()
+                .map(|(key, value)| (key, value.into_iter()))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots() {
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0., 0.05, 0.1];
+        for (k, part) in ["M1cell", "GIR"].into_iter().enumerate() {
+            monitors.forces_and_moments.insert(
+                part.into(),
+                (0..3)
+                    .map(|i| Exertion::from(([0.; 3], ([(i + k) as f64; 3], [-1.; 3]))))
+                    .collect(),
+            );
+        }
+        let snapshots: Vec<_> = monitors.snapshots().collect();
+        assert_eq!(snapshots.len(), 3);
+        assert_eq!(snapshots[1].0, 0.05);
+        assert_eq!(snapshots[1].1["M1cell"].force.x, Some(1.));
+        assert_eq!(
+            monitors.flat_snapshot(2).unwrap().1,
+            vec![3., 3., 3., -1., -1., -1., 2., 2., 2., -1., -1., -1.]
+        );
+        assert!(monitors.snapshot(3).is_none());
+        let par: Vec<_> = monitors.par_flat_snapshots().collect();
+        assert_eq!(par, monitors.flat_snapshots().collect::<Vec<_>>());
+        let sum: f64 = monitors
+            .par_snapshots()
+            .map(|(_, s)| s.values().filter_map(|e| e.force.x).sum::<f64>())
+            .sum();
+        assert_eq!(sum, 9.);
+
+        monitors.forces_and_moments.get_mut("GIR").unwrap().pop();
+        let (_, last) = monitors.flat_snapshot(2).unwrap();
+        assert!(last[..6].iter().all(|x| x.is_nan()));
+        let owned: Vec<_> = monitors.into_iter().collect();
+        assert_eq!(owned[2].1.len(), 1);
+        assert_eq!(owned[0].1["GIR"].force.x, Some(1.));
+    }
+}
@@ -0,0 +1,266 @@
+//! Monitors statistics
+//!
+//! [Monitors::stats] computes the statistics of each monitor over the last seconds of the time series.
+//! The statistics are serializable and rendered as text by [Monitors::summary]
+//! and as LaTeX tables by [Monitors::htc_latex_table], [Monitors::force_latex_table]
+//! and [Monitors::moment_latex_table].
+
+use super::{Exertion, Monitors, Series};
+use crate::Vector;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// 5th, 25th, 50th, 75th and 95th percentiles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Percentiles {
+    pub p5: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+}
+
+/// Statistics of a time series
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Stats {
+    pub mean: f64,
+    /// Standard deviation
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Root mean square
+    pub rms: f64,
+    pub percentiles: Percentiles,
+}
+impl Stats {
+    /// Returns the statistics of the values, `None` if there is no value
+    pub fn new(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std = (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+        let rms = (values.iter().map(|x| x * x).sum::<f64>() / n).sqrt();
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let x = p * (sorted.len() - 1) as f64;
+            let (i, w) = (x.floor() as usize, x.fract());
+            if i + 1 < sorted.len() {
+                sorted[i] + (sorted[i + 1] - sorted[i]) * w
+            } else {
+                sorted[i]
+            }
+        };
+        Some(Self {
+            mean,
+            std,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            rms,
+            percentiles: Percentiles {
+                p5: percentile(0.05),
+                p25: percentile(0.25),
+                p50: percentile(0.5),
+                p75: percentile(0.75),
+                p95: percentile(0.95),
+            },
+        })
+    }
+}
+
+/// Statistics of the components and of the magnitude of a vector time series
+///
+/// The statistics of a component are `None` if the component is missing
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VectorStats {
+    pub x: Option<Stats>,
+    pub y: Option<Stats>,
+    pub z: Option<Stats>,
+    pub magnitude: Option<Stats>,
+}
+impl VectorStats {
+    pub fn new(values: &[Vector]) -> Self {
+        let component = |c: fn(&Vector) -> Option<f64>| {
+            values
+                .iter()
+                .map(c)
+                .collect::<Option<Vec<f64>>>()
+                .and_then(|x| Stats::new(&x))
+        };
+        Self {
+            x: component(|v| v.x),
+            y: component(|v| v.y),
+            z: component(|v| v.z),
+            magnitude: component(Vector::magnitude),
+        }
+    }
+    fn components(&self) -> Option<[&Stats; 3]> {
+        Some([self.x.as_ref()?, self.y.as_ref()?, self.z.as_ref()?])
+    }
+    /// Returns the mean of the components
+    pub fn mean(&self) -> Option<[f64; 3]> {
+        self.components().map(|c| c.map(|s| s.mean))
+    }
+    /// Returns the standard deviation of the components
+    pub fn std(&self) -> Option<[f64; 3]> {
+        self.components().map(|c| c.map(|s| s.std))
+    }
+    /// Returns the magnitude of the mean vector
+    pub fn mean_magnitude(&self) -> Option<f64> {
+        self.mean()
+            .map(|m| m.iter().map(|x| x * x).sum::<f64>().sqrt())
+    }
+    /// Returns the root sum square of the standard deviation of the components
+    pub fn std_magnitude(&self) -> Option<f64> {
+        self.std()
+            .map(|s| s.iter().map(|x| x * x).sum::<f64>().sqrt())
+    }
+}
+
+/// Statistics of the force and of the moment
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ExertionStats {
+    pub force: VectorStats,
+    pub moment: VectorStats,
+}
+impl ExertionStats {
+    pub fn new(values: &[Exertion]) -> Self {
+        let force: Vec<Vector> = values.iter().map(|e| e.force.clone()).collect();
+        let moment: Vec<Vector> = values.iter().map(|e| e.moment.clone()).collect();
+        Self {
+            force: VectorStats::new(&force),
+            moment: VectorStats::new(&moment),
+        }
+    }
+}
+
+/// Statistics of a generic monitor
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SeriesStats {
+    Scalar(Option<Stats>),
+    Vector(Box<VectorStats>),
+}
+
+/// Statistics of all the monitors
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MonitorsStats {
+    /// Time range of the statistics [s]
+    pub time_range: (f64, f64),
+    /// Number of samples
+    pub samples: usize,
+    pub heat_transfer_coefficients: BTreeMap<String, Option<Stats>>,
+    pub forces_and_moments: BTreeMap<String, ExertionStats>,
+    /// Statistics of the sum of the forces and of the moments
+    pub total: Option<ExertionStats>,
+    /// Statistics of the other monitors, per physical quantity
+    pub quantities: BTreeMap<String, BTreeMap<String, SeriesStats>>,
+}
+
+impl Monitors {
+    /// Returns the statistics of the monitors over the last `window` seconds
+    ///
+    /// The statistics are computed over the whole time series if `window` is infinite
+    pub fn stats(&self, window: f64) -> MonitorsStats {
+        let Some(&end) = self.time.last() else {
+            return MonitorsStats::default();
+        };
+        let i = self.last_index(window);
+        let i = i.min(self.len() - 1);
+        let tail = |n: usize| i.min(n)..n;
+
+        let mut total: Option<Vec<Exertion>> = None;
+        for values in self.forces_and_moments.values() {
+            let values = &values[tail(values.len())];
+            total = Some(match total {
+                None => values.to_vec(),
+                Some(total) => total.iter().zip(values).map(|(a, b)| a + b).collect(),
+            });
+        }
+        MonitorsStats {
+            time_range: (self.time[i], end),
+            samples: self.len() - i,
+            heat_transfer_coefficients: self
+                .heat_transfer_coefficients
+                .iter()
+                .map(|(key, values)| (key.clone(), Stats::new(&values[tail(values.len())])))
+                .collect(),
+            forces_and_moments: self
+                .forces_and_moments
+                .iter()
+                .map(|(key, values)| (key.clone(), ExertionStats::new(&values[tail(values.len())])))
+                .collect(),
+            total: total.map(|total| ExertionStats::new(&total)),
+            quantities: self
+                .quantities
+                .iter()
+                .map(|(quantity, q)| {
+                    (
+                        quantity.clone(),
+                        q.monitors
+                            .iter()
+                            .map(|(key, series)| {
+                                let stats = match series {
+                                    Series::Scalar(values) => {
+                                        SeriesStats::Scalar(Stats::new(&values[tail(values.len())]))
+                                    }
+                                    Series::Vector(values) => SeriesStats::Vector(Box::new(
+                                        VectorStats::new(&values[tail(values.len())]),
+                                    )),
+                                };
+                                (key.clone(), stats)
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let s = Stats::new(&[1., 2., 3., 4., 5.]).unwrap();
+        assert_eq!((s.mean, s.min, s.max), (3., 1., 5.));
+        assert!((s.std - 2f64.sqrt()).abs() < 1e-12);
+        assert!((s.rms - 11f64.sqrt()).abs() < 1e-12);
+        assert_eq!(s.percentiles.p50, 3.);
+        assert_eq!(s.percentiles.p25, 2.);
+        assert!(Stats::new(&[]).is_none());
+
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0., 0.5, 1., 1.5, 2.];
+        monitors
+            .heat_transfer_coefficients
+            .insert("M2".into(), vec![9., 9., 1., 2., 3.]);
+        monitors.forces_and_moments.insert(
+            "Cabs".into(),
+            [3., 3., 3., 3., 3.]
+                .iter()
+                .map(|&x| Exertion {
+                    force: [x, 4., 0.].into(),
+                    moment: Vector::from_x(x),
+                    cop: None,
+                })
+                .collect(),
+        );
+        let stats = monitors.stats(1.);
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.time_range, (1., 2.));
+        assert_eq!(stats.heat_transfer_coefficients["M2"].unwrap().mean, 2.);
+        let cabs = &stats.forces_and_moments["Cabs"];
+        assert_eq!(cabs.force.mean_magnitude(), Some(5.));
+        assert_eq!(cabs.force.magnitude.unwrap().max, 5.);
+        assert!(cabs.moment.y.is_none());
+        assert_eq!(
+            stats.total.as_ref().unwrap().force.mean(),
+            Some([3., 4., 0.])
+        );
+        assert_eq!(monitors.stats(f64::INFINITY).samples, 5);
+    }
+}
@@ -0,0 +1,205 @@
+//! Monitors validation
+//!
+//! Each force and moment component of an [Exertion] is read from its own column of the monitors file,
+//! so a part missing a column, e.g. `M1cellMom_Y`, or with missing records ends up with partially filled exertions.
+//! [Monitors::validate] reports, per part, the components that are missing altogether
+//! and the number of samples of each component that differ from the number of time steps.
+//!
+//! [MonitorsLoader::validation](super::MonitorsLoader::validation) sets what the loader does with the report,
+//! see [Validation].
+
+use super::{Exertion, Monitors, MonitorsError};
+use std::fmt;
+
+/// Monitors validation policy
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Validation {
+    /// Logs a warning for each invalid part and leaves the monitors untouched
+    #[default]
+    Warn,
+    /// Logs a warning for each invalid part and sets the missing components and samples to the given value
+    Fill(f64),
+    /// Returns [MonitorsError::Validation] if any part is invalid
+    Strict,
+}
+
+/// Validation report of a part
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub part: String,
+    /// Number of time steps
+    pub expected: usize,
+    /// Number of exertions
+    pub length: usize,
+    /// Number of samples of the `[Fx,Fy,Fz,Mx,My,Mz]` components
+    pub samples: [usize; 6],
+}
+impl PartReport {
+    /// Returns the components without any sample
+    pub fn missing(&self) -> Vec<&'static str> {
+        Exertion::COMPONENTS
+            .iter()
+            .zip(&self.samples)
+            .filter(|(_, &n)| n == 0)
+            .map(|(&c, _)| c)
+            .collect()
+    }
+    /// Returns the components, and their number of samples, with some but not all the samples
+    pub fn incomplete(&self) -> Vec<(&'static str, usize)> {
+        Exertion::COMPONENTS
+            .iter()
+            .zip(&self.samples)
+            .filter(|(_, &n)| n > 0 && n != self.expected)
+            .map(|(&c, &n)| (c, n))
+            .collect()
+    }
+    pub fn is_valid(&self) -> bool {
+        self.length == self.expected && self.samples.iter().all(|&n| n == self.expected)
+    }
+}
+impl fmt::Display for PartReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.part)?;
+        if self.length > self.expected {
+            write!(f, " {} samples instead of {}", self.length, self.expected)?;
+        }
+        let missing = self.missing();
+        if !missing.is_empty() {
+            write!(f, " missing {}", missing.join(", "))?;
+        }
+        for (c, n) in self.incomplete() {
+            write!(f, " {c} has {n} samples instead of {}", self.expected)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validation report of the parts with missing components or samples
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport(pub Vec<PartReport>);
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &PartReport> {
+        self.0.iter()
+    }
+}
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &self.0 {
+            writeln!(f, "{part}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Monitors {
+    /// Returns the parts with missing force and moment components or samples
+    pub fn validate(&self) -> ValidationReport {
+        let expected = self.len();
+        ValidationReport(
+            self.forces_and_moments
+                .iter()
+                .map(|(part, values)| {
+                    let mut samples = [0usize; 6];
+                    for e in values.iter().take(expected) {
+                        samples
+                            .iter_mut()
+                            .zip(e.components())
+                            .filter(|(_, x)| x.is_some())
+                            .for_each(|(n, _)| *n += 1);
+                    }
+                    PartReport {
+                        part: part.clone(),
+                        expected,
+                        length: values.len(),
+                        samples,
+                    }
+                })
+                .filter(|report| !report.is_valid())
+                .collect(),
+        )
+    }
+    /// Sets the missing force and moment components to `value`
+    ///
+    /// The parts with less samples than time steps are padded, and the ones with more are truncated
+    pub fn fill_gaps(&mut self, value: f64) -> &mut Self {
+        let n = self.len();
+        for values in self.forces_and_moments.values_mut() {
+            values.resize_with(n, Default::default);
+            for e in values.iter_mut() {
+                e.components_mut()
+                    .into_iter()
+                    .filter(|x| x.is_none())
+                    .for_each(|x| *x = Some(value));
+            }
+        }
+        self
+    }
+    /// Applies the validation policy
+    pub(crate) fn validation(mut self, validation: Validation) -> Result<Self, MonitorsError> {
+        let report = self.validate();
+        if report.is_valid() {
+            return Ok(self);
+        }
+        if validation == Validation::Strict {
+            return Err(MonitorsError::Validation(report));
+        }
+        for part in report.iter() {
+            log::warn!("invalid monitor {part}");
+        }
+        if let Validation::Fill(value) = validation {
+            self.fill_gaps(value);
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector;
+
+    #[test]
+    fn validation() {
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0., 0.05, 0.1];
+        monitors.forces_and_moments.insert(
+            "M1cell".into(),
+            vec![Exertion::from(([0.; 3], ([1.; 3], [2.; 3]))); 3],
+        );
+        let mut partial = Exertion::from_force(Vector::from([1., 2., 3.]));
+        partial.moment.x = Some(4.);
+        partial.moment.y = Some(5.);
+        let mut values = vec![partial; 2];
+        values[1].force.z = None;
+        monitors.forces_and_moments.insert("GIR".into(), values);
+
+        let report = monitors.validate();
+        assert_eq!(report.0.len(), 1);
+        let part = &report.0[0];
+        assert_eq!(part.part, "GIR");
+        assert_eq!(part.missing(), vec!["Mz"]);
+        assert_eq!(
+            part.incomplete(),
+            vec![("Fx", 2), ("Fy", 2), ("Fz", 1), ("Mx", 2), ("My", 2)]
+        );
+
+        assert!(matches!(
+            monitors.clone().validation(Validation::Strict),
+            Err(MonitorsError::Validation(r)) if r == report
+        ));
+        let filled = monitors.validation(Validation::Fill(0.)).unwrap();
+        assert!(filled.validate().is_valid());
+        assert_eq!(
+            filled.forces_and_moments["GIR"][1].force,
+            Vector::from([1., 2., 0.])
+        );
+        assert_eq!(filled.forces_and_moments["GIR"][2].moment, Vector::zero());
+
+        let mut monitors = filled;
+        monitors.time.pop();
+        assert_eq!(monitors.validate().0[0].length, 3);
+    }
+}
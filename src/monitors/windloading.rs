@@ -0,0 +1,364 @@
+//! FEM wind loads
+//!
+//! [WindLoading] gathers the forces and moments of the parts of the telescope that are inputs of the FEM model
+//! and writes them to the Parquet table [WINDLOADS] read by [gmt-lom](lom) and by the FEM pipeline.
+//! [WindLoading::to_parquet] never overwrites an existing file,
+//! e.g. the `windloading.parquet` table of the FEM rigid body motions in the CFD case directories.
+//!
+//! Each FEM input is a column of the table named after the input, e.g. `CFD2021106F`,
+//! and each row is a time step holding the list of the forces and moments `[Fx,Fy,Fz,Mx,My,Mz]`
+//! of all the parts of the input, in the order of the parts.
+//! The `Time` column holds the time vector.
+//!
+//! Each [FemInput] has an explicit ordered list of parts, and a part missing from the monitors is an error.
+//! The lists of the M1 covers and of the M1 and M2 segments inputs are built in,
+//! the other inputs, e.g. the mount input, are loaded with [FemInput::from_path] from a TOML or a JSON file:
+//! ```toml
+//! [[input]]
+//! name = "CFD2021106F"
+//! parts = ["Cring_1", "Cring_2", "GIR", "M1cell"]
+//! ```
+//!
+//! ```no_run
+//! use parse_monitors::{windloading::{FemInput, WindLoading, WINDLOADS}, Mirror, MonitorsLoader};
+//!
+//! let path = "/fsx/CASES/zen30az000_OS7";
+//! let monitors = MonitorsLoader::<2021>::default().data_path(path).load().unwrap();
+//! let m1 = Mirror::m1(path).load().unwrap();
+//! WindLoading::builder(&monitors)
+//!     .inputs(FemInput::from_path("fem_inputs.toml").unwrap())
+//!     .input(FemInput::m2_segments())
+//!     .mirror(&m1)
+//!     .build()
+//!     .unwrap()
+//!     .to_parquet(std::path::Path::new(path).join(WINDLOADS))
+//!     .unwrap();
+//! ```
+
+use super::{Exertion, Mirror, Monitors};
+use crate::config;
+use arrow::{
+    array::{ArrayRef, Float64Array, ListArray},
+    datatypes::{DataType, Field, Float64Type, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use serde::Deserialize;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Name of the wind loads Parquet table
+pub const WINDLOADS: &str = "windloads.parquet";
+/// Mount FEM input
+const MOUNT: &str = "CFD2021106F";
+/// M1 covers FEM input
+const M1_COVERS: &str = "OSSMirrorCovers6F";
+/// M1 segments FEM input
+const M1_SEGMENTS: &str = "OSSM1Lcl6F";
+/// M2 segments FEM input
+const M2_SEGMENTS: &str = "MCM2LclForce6F";
+
+#[derive(thiserror::Error, Debug)]
+pub enum WindLoadingError {
+    #[error("failed to load the FEM inputs file")]
+    Config(#[from] config::ConfigError),
+    #[error("FEM input {0}: part {1} is missing from the monitors")]
+    MissingPart(String, String),
+    #[error("FEM input {0} has no part")]
+    Empty(String),
+    #[error("FEM input {0}: part {1} is missing force or moment components")]
+    MissingComponent(String, String),
+    #[error("FEM input {0}: {1} time steps instead of {2}")]
+    Length(String, usize, usize),
+    #[error("failed to build the wind loads table")]
+    Arrow(#[from] ArrowError),
+    #[error("{0:?} already exists")]
+    Exists(PathBuf),
+    #[error("failed to write the wind loads table")]
+    Parquet(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+type Result<T> = std::result::Result<T, WindLoadingError>;
+
+/// FEM input
+#[derive(Debug, Clone, Deserialize)]
+pub struct FemInput {
+    name: String,
+    /// Parts of the input, in the order of the FEM model
+    parts: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct InputsFile {
+    input: Vec<FemInput>,
+}
+
+impl FemInput {
+    pub fn new<S, I>(name: S, parts: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            parts: parts.into_iter().map(|part| part.into()).collect(),
+        }
+    }
+    /// Loads the FEM inputs from a TOML or a JSON file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        let InputsFile { input } = config::from_path(path)?;
+        Ok(input)
+    }
+    /// Mount wind loads (`CFD2021106F`) of the given parts, in order
+    pub fn mount<I>(parts: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self::new(MOUNT, parts)
+    }
+    /// M1 outer and inner covers wind loads (`OSSMirrorCovers6F`)
+    pub fn m1_covers() -> Self {
+        Self::new(
+            M1_COVERS,
+            [
+                "M1cov1", "M1cov6", "M1cov5", "M1cov4", "M1cov3", "M1cov2", "M1covin2", "M1covin1",
+                "M1covin6", "M1covin5", "M1covin4", "M1covin3",
+            ],
+        )
+    }
+    /// M2 segments wind loads (`MCM2LclForce6F`) from the monitors `M2s1` to `M2s7`
+    pub fn m2_segments() -> Self {
+        Self::new(M2_SEGMENTS, (1..=7).map(|i| format!("M2s{i}")))
+    }
+    /// M1 segments wind loads (`OSSM1Lcl6F`) from [Mirror::M1]
+    pub fn m1_segments() -> Self {
+        Self::segments(M1_SEGMENTS)
+    }
+    /// Segments S1 to S7 of a [Mirror]
+    fn segments(name: &str) -> Self {
+        Self::new(name, (1..=7).map(|i| format!("S{i}")))
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn parts(&self) -> &[String] {
+        &self.parts
+    }
+    /// Checks that all the parts of the input are among the given ones
+    fn check<'a, I>(&self, parts: I) -> Result<()>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        if self.parts.is_empty() {
+            return Err(WindLoadingError::Empty(self.name.clone()));
+        }
+        let parts: Vec<&String> = parts.into_iter().collect();
+        match self.parts.iter().find(|p| !parts.contains(p)) {
+            Some(missing) => Err(WindLoadingError::MissingPart(
+                self.name.clone(),
+                missing.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+    /// Returns the forces and moments of the input parts at each time step
+    fn loads<'a, F, I>(&self, n: usize, exertion: F) -> Result<Vec<Vec<f64>>>
+    where
+        F: Fn(&str) -> I,
+        I: IntoIterator<Item = &'a Exertion>,
+    {
+        let mut loads = vec![Vec::with_capacity(6 * self.parts.len()); n];
+        for part in &self.parts {
+            let mut m = 0;
+            for (load, e) in loads.iter_mut().zip(exertion(part)) {
+                let fm = e
+                    .components()
+                    .into_iter()
+                    .collect::<Option<Vec<f64>>>()
+                    .ok_or_else(|| {
+                        WindLoadingError::MissingComponent(self.name.clone(), part.clone())
+                    })?;
+                load.extend(fm);
+                m += 1;
+            }
+            if m != n {
+                return Err(WindLoadingError::Length(self.name.clone(), m, n));
+            }
+        }
+        Ok(loads)
+    }
+}
+
+/// FEM wind loads table
+#[derive(Debug, Clone, Default)]
+pub struct WindLoading {
+    pub time: Vec<f64>,
+    /// FEM inputs name, parts and loads at each time step
+    pub inputs: Vec<(String, Vec<String>, Vec<Vec<f64>>)>,
+}
+impl WindLoading {
+    /// Returns a [WindLoading] builder without any FEM input
+    pub fn builder(monitors: &Monitors) -> WindLoadingBuilder<'_> {
+        WindLoadingBuilder {
+            monitors,
+            mirror: None,
+            inputs: vec![],
+        }
+    }
+    /// Returns the wind loads as an Arrow record
+    pub fn to_record(&self) -> Result<RecordBatch> {
+        let list = || DataType::List(Arc::new(Field::new("item", DataType::Float64, true)));
+        let mut fields = vec![Field::new("Time", DataType::Float64, false)];
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(Float64Array::from(self.time.clone()))];
+        for (name, _, loads) in self.inputs.iter() {
+            fields.push(Field::new(name, list(), false));
+            columns.push(Arc::new(
+                ListArray::from_iter_primitive::<Float64Type, _, _>(
+                    loads
+                        .iter()
+                        .map(|load| Some(load.iter().map(|&x| Some(x)).collect::<Vec<_>>())),
+                ),
+            ));
+        }
+        Ok(RecordBatch::try_new(
+            Arc::new(Schema::new(fields)),
+            columns,
+        )?)
+    }
+    /// Writes the wind loads to a new Parquet file
+    ///
+    /// Returns [WindLoadingError::Exists] if the file already exists
+    pub fn to_parquet<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            return Err(WindLoadingError::Exists(path.to_path_buf()));
+        }
+        lom::Table::from(self.to_record()?)
+            .to_parquet(path)
+            .map_err(|e| WindLoadingError::Parquet(Box::new(e)))
+    }
+}
+
+/// [WindLoading] builder
+pub struct WindLoadingBuilder<'a> {
+    monitors: &'a Monitors,
+    mirror: Option<&'a Mirror>,
+    inputs: Vec<FemInput>,
+}
+impl<'a> WindLoadingBuilder<'a> {
+    /// Sets the FEM inputs from the monitors
+    pub fn inputs(self, inputs: Vec<FemInput>) -> Self {
+        Self { inputs, ..self }
+    }
+    /// Adds a FEM input from the monitors
+    pub fn input(mut self, input: FemInput) -> Self {
+        self.inputs.push(input);
+        self
+    }
+    /// Adds the M1 or M2 segments loads of a [Mirror]
+    ///
+    /// The M2 segments loads of [Mirror::M2] replace the M2 segments monitors
+    pub fn mirror(self, mirror: &'a Mirror) -> Self {
+        Self {
+            mirror: Some(mirror),
+            ..self
+        }
+    }
+    pub fn build(self) -> Result<WindLoading> {
+        let n = self.monitors.len();
+        let fm = &self.monitors.forces_and_moments;
+        let mirror_input = self.mirror.map(|mirror| match mirror {
+            Mirror::M1 { .. } => FemInput::m1_segments(),
+            Mirror::M2 { .. } => FemInput::segments(M2_SEGMENTS),
+        });
+        let mut windloading = WindLoading {
+            time: self.monitors.time.clone(),
+            ..Default::default()
+        };
+        for input in self.inputs.iter().filter(|input| {
+            mirror_input
+                .as_ref()
+                .is_none_or(|mirror| mirror.name != input.name)
+        }) {
+            input.check(fm.keys())?;
+            let loads = input.loads(n, |part| fm[part].iter())?;
+            windloading
+                .inputs
+                .push((input.name.clone(), input.parts.clone(), loads));
+        }
+        if let (Some(mirror), Some(input)) = (self.mirror, mirror_input) {
+            let force = mirror.forces_and_moments();
+            input.check(force.keys())?;
+            let loads = input.loads(n, |part| force[part].iter())?;
+            windloading.inputs.push((input.name, input.parts, loads));
+        }
+        Ok(windloading)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+
+    #[test]
+    fn parquet() {
+        let mut monitors = Monitors::default();
+        monitors.time = vec![0., 0.05, 0.1];
+        for part in ["M1cell", "Cring_1", "M2s1"] {
+            monitors.forces_and_moments.insert(
+                part.into(),
+                (0..3)
+                    .map(|i| Exertion::from(([0.; 3], ([i as f64; 3], [1.; 3]))))
+                    .collect(),
+            );
+        }
+        let dir = std::env::temp_dir().join(format!("windloading-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let inputs = dir.join("fem_inputs.toml");
+        std::fs::write(
+            &inputs,
+            "[[input]]\nname = \"CFD2021106F\"\nparts = [\"M1cell\", \"Cring_1\"]\n\n\
+             [[input]]\nname = \"MCM2LclForce6F\"\nparts = [\"M2s1\"]\n",
+        )
+        .unwrap();
+        let windloading = WindLoading::builder(&monitors)
+            .inputs(FemInput::from_path(&inputs).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(windloading.inputs[0].0, "CFD2021106F");
+        assert_eq!(windloading.inputs[0].1, vec!["M1cell", "Cring_1"]);
+        assert_eq!(windloading.inputs[1].1, vec!["M2s1"]);
+        assert_eq!(
+            windloading.inputs[0].2[2],
+            vec![2., 2., 2., 1., 1., 1., 2., 2., 2., 1., 1., 1.]
+        );
+        assert!(matches!(
+            WindLoading::builder(&monitors)
+                .input(FemInput::m2_segments())
+                .build(),
+            Err(WindLoadingError::MissingPart(_, part)) if part == "M2s2"
+        ));
+
+        let path = dir.join(WINDLOADS);
+        windloading.to_parquet(&path).unwrap();
+        let table = lom::Table::from_parquet(&path).unwrap();
+        let record = table.table();
+        assert_eq!(record.num_rows(), 3);
+        let column = record
+            .column(record.schema().index_of("MCM2LclForce6F").unwrap())
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap()
+            .value(1);
+        assert_eq!(column.len(), 6);
+        assert!(matches!(
+            windloading.to_parquet(&path),
+            Err(WindLoadingError::Exists(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -6,7 +6,7 @@
 use super::{Record, Result};
 use geotrans::{Segment, SegmentTrait, Transform, TransformMut, M1, M2};
 use serde::Deserialize;
-use std::{fs::File, io::Read, marker::PhantomData, path::PathBuf};
+use std::{marker::PhantomData, path::PathBuf};
 
 fn norm(v: &[f64]) -> f64 {
     v.iter().map(|&x| x * x).sum::<f64>().sqrt()
@@ -153,22 +153,9 @@ where
         );
         Ok(this)
     }
-    #[cfg(feature = "bzip2")]
+    /// Reads the pressure file, the compression format is detected from the file contents
     pub fn decompress(path: PathBuf) -> Result<String> {
-        let csv_file = File::open(path)?;
-        let buf = std::io::BufReader::new(csv_file);
-        let mut bz2 = bzip2::bufread::BzDecoder::new(buf);
-        let mut contents = String::new();
-        bz2.read_to_string(&mut contents)?;
-        Ok(contents)
-    }
-    #[cfg(not(feature = "bzip2"))]
-    pub fn decompress(path: PathBuf) -> Result<String> {
-        let csv_file = File::open(path)?;
-        let mut gz = flate2::read::GzDecoder::new(csv_file);
-        let mut contents = String::new();
-        gz.read_to_string(&mut contents)?;
-        Ok(contents)
+        Ok(crate::decompress::read_to_string(path)?)
     }
     /// Loads the pressure from a csv bz2-compressed file
     pub fn load_pressure(contents: String) -> Result<Self> {
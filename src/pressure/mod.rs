@@ -38,9 +38,8 @@ pub use telescope::*;
 
 #[derive(thiserror::Error, Debug)]
 pub enum PressureError {
-    #[cfg(feature = "bzip2")]
     #[error("Failed to decompress the file")]
-    Decompress(#[from] bzip2::Error),
+    Decompress(#[from] crate::decompress::DecompressError),
     #[error("Failed to open the pressure file")]
     Io(#[from] std::io::Error),
     #[error("Failed to deserialize the CSV file")]
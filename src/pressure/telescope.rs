@@ -1,7 +1,7 @@
 use super::{Record, Result};
-use flate2::read::GzDecoder;
+use crate::decompress;
 use itertools::{Itertools, MinMaxResult::MinMax};
-use std::{cmp::Ordering, fmt::Display, fs::File, io::Read, path::Path};
+use std::{cmp::Ordering, fmt::Display, path::Path};
 
 fn partition(data: &[f64]) -> Option<(Vec<f64>, f64, Vec<f64>)> {
     match data.len() {
@@ -129,10 +129,7 @@ impl Telescope {
     /// Loads the pressure data
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
         let data_path = Path::new(path.as_ref());
-        let mut contents = String::new();
-        let csv_file = File::open(&data_path)?;
-        let mut gz = GzDecoder::new(csv_file);
-        gz.read_to_string(&mut contents)?;
+        let contents = decompress::read_to_string(data_path)?;
         let mut rdr = csv::Reader::from_reader(contents.as_bytes());
         let mut telescope: Telescope = Telescope {
             filename: String::from(data_path.file_name().map(|x| x.to_str()).flatten().unwrap()),
@@ -28,11 +28,13 @@ impl DomeSeeingPart {
         &self,
         cfd_cases_21: Vec<CfdCase<2021>>,
         truncate: Option<(Option<CfdCase<2021>>, usize)>,
-    ) -> String {
+    ) -> Result<String, Box<dyn Error>> {
+        let cfd_root_21 = cfd::Baseline::<2021>::default_path()?;
+        let cfd_root_20 = cfd::Baseline::<OTHER_YEAR>::default_path()?;
         let results: Vec<_> = cfd_cases_21
             .into_par_iter()
             .map(|cfd_case_21| {
-                let path_to_case = cfd::Baseline::<2021>::path().join(format!("{}", cfd_case_21));
+                let path_to_case = cfd_root_21.join(format!("{}", cfd_case_21));
                 let mut ds_21 = DomeSeeing::load(path_to_case.clone()).unwrap();
                 match &truncate {
                     Some((Some(cfd_case), len)) => {
@@ -49,11 +51,9 @@ impl DomeSeeingPart {
                     Some((
                         (cfd_case_21, wfe_rms, v_pssn, h_pssn),
                         if let Some(cfd_case_20) = cfd::Baseline::<OTHER_YEAR>::find(cfd_case_21) {
-                            let ds_20 = DomeSeeing::load(
-                                cfd::Baseline::<OTHER_YEAR>::path()
-                                    .join(format!("{}", cfd_case_20)),
-                            )
-                            .unwrap();
+                            let ds_20 =
+                                DomeSeeing::load(cfd_root_20.join(format!("{}", cfd_case_20)))
+                                    .unwrap();
                             if let (Some(v_pssn), Some(h_pssn)) =
                                 (ds_20.pssn(Band::V), ds_20.pssn(Band::H))
                             {
@@ -104,7 +104,7 @@ impl DomeSeeingPart {
                 _ => unimplemented!(),
             })
             .collect::<Vec<String>>();
-        table_content.join("\n")
+        Ok(table_content.join("\n"))
     }
 }
 impl super::Report<2021> for DomeSeeingPart {
@@ -114,7 +114,7 @@ impl super::Report<2021> for DomeSeeingPart {
         cfd_case: CfdCase<2021>,
         ri_pic_idx: Option<usize>,
     ) -> Result<String, Box<dyn Error>> {
-        let path_to_case = Baseline::<2021>::path().join(&cfd_case.to_string());
+        let path_to_case = Baseline::<2021>::default_path()?.join(&cfd_case.to_string());
         let pattern = path_to_case
             .join("scenes")
             .join("RI_tel_RI_tel*.png")
@@ -167,6 +167,7 @@ impl super::Report<2021> for DomeSeeingPart {
             cfd::ZenithAngle::Zero => part + "chapter1.tex",
             cfd::ZenithAngle::Thirty => part + "chapter2.tex",
             cfd::ZenithAngle::Sixty => part + "chapter3.tex",
+            cfd::ZenithAngle::Custom(_) => part + &format!("chapter.{}.tex", zenith_angle),
         };
         let mut file = File::create(report_path.join(chapter_filename))?;
         write!(
@@ -194,7 +195,7 @@ impl super::Report<2021> for DomeSeeingPart {
                     })
                     .collect::<Vec<cfd::CfdCase<2021>>>(),
                 None
-            ),
+            )?,
             cfd::Baseline::<2021>::at_zenith(zenith_angle)
                 .into_iter()
                 .filter(|cfd_case| if let Some(cases) = cfd_cases_subset {
@@ -255,7 +256,7 @@ impl DomeSeeingPart {
 {}
 "#,
             name,
-            self.chapter_table(cfd_cases, Some((Some(trouble_maker), cut_len))),
+            self.chapter_table(cfd_cases, Some((Some(trouble_maker), cut_len)))?,
             results.join("\n")
         )?;
         Ok(chapter_filename)
@@ -21,7 +21,7 @@ impl super::Report<2021> for HTC {
         cfd_case: cfd::CfdCase<2021>,
         _: Option<usize>,
     ) -> Result<String, Box<dyn Error>> {
-        let path_to_case = cfd::Baseline::<2021>::path().join(&cfd_case.to_string());
+        let path_to_case = cfd::Baseline::<2021>::default_path()?.join(&cfd_case.to_string());
         let monitors = MonitorsLoader::<2021>::default()
             .data_path(path_to_case)
             .load()?;
@@ -52,6 +52,7 @@ impl super::Report<2021> for HTC {
             cfd::ZenithAngle::Zero => part + "chapter1.tex",
             cfd::ZenithAngle::Thirty => part + "chapter2.tex",
             cfd::ZenithAngle::Sixty => part + "chapter3.tex",
+            cfd::ZenithAngle::Custom(_) => part + &format!("chapter.{}.tex", zenith_angle),
         };
         let cfd_cases = cfd::Baseline::<2021>::at_zenith(zenith_angle)
             .into_iter()
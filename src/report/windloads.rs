@@ -59,7 +59,7 @@ impl super::Report<2021> for WindLoads {
         cfd_case: cfd::CfdCase<2021>,
         _: Option<usize>,
     ) -> Result<String, Box<dyn Error>> {
-        let path_to_case = cfd::Baseline::<2021>::path().join(&cfd_case.to_string());
+        let path_to_case = cfd::Baseline::<2021>::default_path()?.join(&cfd_case.to_string());
         let pattern = path_to_case
             .join("scenes")
             .join("vort_tel_vort_tel*.png")
@@ -321,6 +321,7 @@ impl super::Report<2021> for WindLoads {
             cfd::ZenithAngle::Zero => part + "chapter1.tex",
             cfd::ZenithAngle::Thirty => part + "chapter2.tex",
             cfd::ZenithAngle::Sixty => part + "chapter3.tex",
+            cfd::ZenithAngle::Custom(_) => part + &format!("chapter.{}.tex", zenith_angle),
         };
         let cfd_cases = cfd::Baseline::<2021>::at_zenith(zenith_angle)
             .into_iter()
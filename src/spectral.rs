@@ -0,0 +1,402 @@
+//! Power spectral densities of the monitors
+//!
+//! The spectra are estimated with Welch method: the time series are split into overlapping segments,
+//! each segment is detrended, windowed and Fourier transformed, and the periodograms of the segments are averaged.
+//! The spectra are one-sided densities, i.e. the PSD of a signal integrates over frequency to the signal variance.
+//!
+//! The PSDs of the force and moment components of each element, and the cross-spectra between the components of an element,
+//! are computed with [Monitors::spectra] and [Mirror::spectra].
+//! The frequency axis is derived from the sampling rate of the monitors time axis.
+//!
+//! ```no_run
+//! use parse_monitors::{spectral::{Welch, Window}, MonitorsLoader};
+//!
+//! let monitors = MonitorsLoader::<2021>::default()
+//!     .data_path("/fsx/CASES/zen30az000_OS7")
+//!     .header_filter("M1cell")
+//!     .load()
+//!     .unwrap();
+//! let spectra = monitors
+//!     .spectra(&Welch::default().window(Window::Hann).segment_length(2048))
+//!     .unwrap();
+//! spectra.to_csv("monitors_psd.csv").unwrap();
+//! ```
+
+use crate::{export::column_name, Exertion, Mirror, Monitors};
+use npyz::{npz::NpzWriter, WriterBuilder};
+pub use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::{collections::BTreeMap, io, path::Path};
+
+#[derive(thiserror::Error, Debug)]
+pub enum SpectralError {
+    #[error("cannot derive the sampling rate from a time axis of {0} samples")]
+    SamplingRate(usize),
+    #[error("the time series length ({0}) does not match the time axis length ({1})")]
+    Length(usize, usize),
+    #[error("failed to write the spectra")]
+    Io(#[from] io::Error),
+    #[error("failed to write the spectra to CSV")]
+    Csv(#[from] csv::Error),
+}
+type Result<T> = std::result::Result<T, SpectralError>;
+
+/// Segments window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Window {
+    Rectangular,
+    #[default]
+    Hann,
+    Hamming,
+}
+impl Window {
+    /// Returns the window coefficients for a segment of length `n`
+    pub fn coefficients(&self, n: usize) -> Vec<f64> {
+        let cos = |i: usize| (2. * std::f64::consts::PI * i as f64 / n as f64).cos();
+        match self {
+            Window::Rectangular => vec![1f64; n],
+            Window::Hann => (0..n).map(|i| 0.5 - 0.5 * cos(i)).collect(),
+            Window::Hamming => (0..n).map(|i| 0.54 - 0.46 * cos(i)).collect(),
+        }
+    }
+}
+
+/// Segments detrending
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Detrend {
+    None,
+    /// Removes the mean
+    #[default]
+    Constant,
+    /// Removes the least-square linear fit
+    Linear,
+}
+impl Detrend {
+    fn apply(&self, x: &mut [f64]) {
+        let n = x.len() as f64;
+        match self {
+            Detrend::None => (),
+            Detrend::Constant => {
+                let mean = x.iter().sum::<f64>() / n;
+                x.iter_mut().for_each(|x| *x -= mean);
+            }
+            Detrend::Linear => {
+                let t_mean = 0.5 * (n - 1.);
+                let mean = x.iter().sum::<f64>() / n;
+                let (num, den) = x
+                    .iter()
+                    .enumerate()
+                    .fold((0f64, 0f64), |(num, den), (i, x)| {
+                        let t = i as f64 - t_mean;
+                        (num + t * (x - mean), den + t * t)
+                    });
+                let slope = if den > 0. { num / den } else { 0. };
+                x.iter_mut()
+                    .enumerate()
+                    .for_each(|(i, x)| *x -= mean + slope * (i as f64 - t_mean));
+            }
+        }
+    }
+}
+
+/// Welch spectral estimator
+#[derive(Debug, Clone)]
+pub struct Welch {
+    window: Window,
+    segment_length: usize,
+    overlap: f64,
+    detrend: Detrend,
+}
+impl Default for Welch {
+    /// Hann window, segments of 1024 samples overlapping by 50%, mean removed
+    fn default() -> Self {
+        Self {
+            window: Window::default(),
+            segment_length: 1024,
+            overlap: 0.5,
+            detrend: Detrend::default(),
+        }
+    }
+}
+impl Welch {
+    /// Sets the segments window
+    pub fn window(self, window: Window) -> Self {
+        Self { window, ..self }
+    }
+    /// Sets the segment length in number of samples
+    ///
+    /// The segment length is truncated to the length of the time series
+    pub fn segment_length(self, segment_length: usize) -> Self {
+        Self {
+            segment_length: segment_length.max(1),
+            ..self
+        }
+    }
+    /// Sets the overlap between consecutive segments as a fraction of the segment length in [0,1[
+    pub fn overlap(self, overlap: f64) -> Self {
+        Self {
+            overlap: overlap.clamp(0., 0.99),
+            ..self
+        }
+    }
+    /// Sets the segments detrending
+    pub fn detrend(self, detrend: Detrend) -> Self {
+        Self { detrend, ..self }
+    }
+    /// Returns the one-sided frequency axis [Hz] for time series of length `n` sampled at `fs` [Hz]
+    pub fn frequency(&self, n: usize, fs: f64) -> Vec<f64> {
+        let m = self.segment_length.min(n);
+        (0..=m / 2).map(|k| k as f64 * fs / m as f64).collect()
+    }
+    /// Returns the windowed Fourier transforms of the segments of `x`
+    fn segments(&self, x: &[f64]) -> Vec<Vec<Complex<f64>>> {
+        let m = self.segment_length.min(x.len());
+        if m == 0 {
+            return vec![];
+        }
+        let step = (m - (self.overlap * m as f64).round() as usize).max(1);
+        let window = self.window.coefficients(m);
+        let fft = FftPlanner::new().plan_fft_forward(m);
+        (0..=(x.len() - m) / step)
+            .map(|i| {
+                let mut segment = x[i * step..i * step + m].to_vec();
+                self.detrend.apply(&mut segment);
+                let mut buffer: Vec<Complex<f64>> = segment
+                    .iter()
+                    .zip(&window)
+                    .map(|(x, w)| Complex::new(x * w, 0.))
+                    .collect();
+                fft.process(&mut buffer);
+                buffer.truncate(m / 2 + 1);
+                buffer
+            })
+            .collect()
+    }
+    /// Returns the one-sided cross-spectral density of `x` and `y` sampled at `fs` [Hz]
+    ///
+    /// `x` and `y` must have the same length
+    pub fn csd(&self, x: &[f64], y: &[f64], fs: f64) -> Vec<Complex<f64>> {
+        let m = self.segment_length.min(x.len());
+        let xs = self.segments(x);
+        let ys = self.segments(y);
+        let n_segment = xs.len().min(ys.len());
+        if n_segment == 0 {
+            return vec![];
+        }
+        let s2: f64 = self.window.coefficients(m).iter().map(|w| w * w).sum();
+        let scale = 1. / (fs * s2);
+        let mut pxy = vec![Complex::new(0f64, 0f64); m / 2 + 1];
+        for (x, y) in xs.iter().zip(&ys) {
+            pxy.iter_mut()
+                .zip(x.iter().zip(y))
+                .for_each(|(p, (x, y))| *p += x.conj() * y);
+        }
+        pxy.iter_mut().enumerate().for_each(|(k, p)| {
+            // one-sided: the energy of the negative frequencies is folded onto the positive ones
+            let fold = if k == 0 || 2 * k == m { 1. } else { 2. };
+            *p *= fold * scale / n_segment as f64;
+        });
+        pxy
+    }
+    /// Returns the one-sided power spectral density of `x` sampled at `fs` [Hz]
+    pub fn psd(&self, x: &[f64], fs: f64) -> Vec<f64> {
+        self.csd(x, x, fs).into_iter().map(|p| p.re).collect()
+    }
+}
+
+/// Returns the sampling rate [Hz] from the median sampling period of the time axis
+pub fn sampling_frequency(time: &[f64]) -> Result<f64> {
+    crate::median_time_step(time)
+        .map(f64::recip)
+        .ok_or(SpectralError::SamplingRate(time.len()))
+}
+
+/// Power and cross spectral densities
+///
+/// The PSDs are given per channel, a channel is an element and a force or moment component, e.g. `M1cell_Fx`
+/// (see [column_name]).
+/// The cross-spectra are given for each pair of channels of the same element.
+#[derive(Debug, Clone, Default)]
+pub struct Spectra {
+    /// Sampling rate of the time series [Hz]
+    pub sampling_frequency: f64,
+    /// Frequency axis [Hz]
+    pub frequency: Vec<f64>,
+    pub psd: BTreeMap<String, Vec<f64>>,
+    pub csd: BTreeMap<(String, String), Vec<Complex<f64>>>,
+}
+impl Spectra {
+    /// Computes the spectra of the forces and moments of each element
+    fn new<'a, I, E>(welch: &Welch, time: &[f64], elements: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (&'a String, E)>,
+        E: IntoIterator<Item = &'a Exertion>,
+    {
+        let fs = sampling_frequency(time)?;
+        let mut spectra = Spectra {
+            sampling_frequency: fs,
+            frequency: welch.frequency(time.len(), fs),
+            ..Default::default()
+        };
+        for (key, values) in elements {
+            let values: Vec<&Exertion> = values.into_iter().collect();
+            if values.len() != time.len() {
+                return Err(SpectralError::Length(values.len(), time.len()));
+            }
+            // components missing from the monitors are skipped
+            let channels: Vec<(String, Vec<f64>)> = Exertion::COMPONENTS
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, component)| {
+                    values
+                        .iter()
+                        .map(|e| e.components()[i])
+                        .collect::<Option<Vec<f64>>>()
+                        .map(|x| (column_name(key, component), x))
+                })
+                .collect();
+            for (i, (a, x)) in channels.iter().enumerate() {
+                spectra.psd.insert(a.clone(), welch.psd(x, fs));
+                for (b, y) in channels.iter().skip(i + 1) {
+                    spectra
+                        .csd
+                        .insert((a.clone(), b.clone()), welch.csd(x, y, fs));
+                }
+            }
+        }
+        Ok(spectra)
+    }
+    /// Writes the spectra to a CSV file
+    ///
+    /// The first column is the frequency, followed by the PSDs and by the real and imaginary parts of the cross-spectra
+    pub fn to_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut wtr = csv::Writer::from_path(path)?;
+        let mut header = vec![String::from("Frequency [Hz]")];
+        header.extend(self.psd.keys().map(|key| format!("PSD {key}")));
+        header.extend(
+            self.csd
+                .keys()
+                .flat_map(|(a, b)| [format!("CSD {a}/{b} real"), format!("CSD {a}/{b} imag")]),
+        );
+        wtr.write_record(&header)?;
+        for (k, f) in self.frequency.iter().enumerate() {
+            let mut record = vec![f.to_string()];
+            record.extend(self.psd.values().map(|p| p[k].to_string()));
+            record.extend(
+                self.csd
+                    .values()
+                    .flat_map(|p| [p[k].re.to_string(), p[k].im.to_string()]),
+            );
+            wtr.write_record(&record)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+    /// Writes the spectra to a numpy npz file
+    ///
+    /// The archive contains the arrays `frequency`, `sampling_frequency`, `psd:{channel}` and
+    /// `csd:{channel}:{channel}`, the later with the real and imaginary parts in the 2 columns
+    pub fn to_npz<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut npz = NpzWriter::create(path)?;
+        let mut writer = npz
+            .array("frequency", Default::default())?
+            .default_dtype()
+            .shape(&[self.frequency.len() as u64])
+            .begin_nd()?;
+        writer.extend(self.frequency.iter().cloned())?;
+        writer.finish()?;
+        let mut writer = npz
+            .array("sampling_frequency", Default::default())?
+            .default_dtype()
+            .shape(&[1])
+            .begin_nd()?;
+        writer.push(&self.sampling_frequency)?;
+        writer.finish()?;
+        for (key, psd) in self.psd.iter() {
+            let mut writer = npz
+                .array(&format!("psd:{key}"), Default::default())?
+                .default_dtype()
+                .shape(&[psd.len() as u64])
+                .begin_nd()?;
+            writer.extend(psd.iter().cloned())?;
+            writer.finish()?;
+        }
+        for ((a, b), csd) in self.csd.iter() {
+            let mut writer = npz
+                .array(&format!("csd:{a}:{b}"), Default::default())?
+                .default_dtype()
+                .shape(&[csd.len() as u64, 2])
+                .begin_nd()?;
+            writer.extend(csd.iter().flat_map(|p| [p.re, p.im]))?;
+            writer.finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl Monitors {
+    /// Returns the spectra of the forces and moments of each element
+    pub fn spectra(&self, welch: &Welch) -> Result<Spectra> {
+        Spectra::new(welch, &self.time, self.forces_and_moments.iter())
+    }
+}
+impl Mirror {
+    /// Returns the spectra of the forces and moments of each segment
+    pub fn spectra(&self, welch: &Welch) -> Result<Spectra> {
+        let time: Vec<f64> = self.time().iter().cloned().collect();
+        Spectra::new(welch, &time, self.forces_and_moments().iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welch() {
+        // 2.5Hz sine wave sampled at 20Hz: 8 periods per segment of 64 samples
+        let fs = 20.;
+        let x: Vec<f64> = (0..1024)
+            .map(|i| 3. * (2. * std::f64::consts::PI * 2.5 * i as f64 / fs).sin() + 1.)
+            .collect();
+        let welch = Welch::default().segment_length(64);
+        let psd = welch.psd(&x, fs);
+        let frequency = welch.frequency(x.len(), fs);
+        assert_eq!(psd.len(), 33);
+        assert_eq!(frequency[1], fs / 64.);
+        let (k, _) = psd
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        assert_eq!(frequency[k], 2.5);
+        // variance
+        let df = frequency[1];
+        assert!((psd.iter().sum::<f64>() * df - 4.5).abs() < 1e-9);
+
+        let y: Vec<f64> = x.iter().map(|x| -2. * x).collect();
+        let csd = welch.csd(&x, &y, fs);
+        assert!((csd[k].re + 2. * psd[k]).abs() < 1e-9);
+
+        let mut monitors = Monitors::default();
+        monitors.time = (0..1024).map(|i| i as f64 / fs).collect();
+        monitors.forces_and_moments.insert(
+            "M1cell".into(),
+            x.iter()
+                .map(|&x| Exertion {
+                    force: [x, 0., 1.].into(),
+                    moment: crate::Vector::from_x(x),
+                    cop: None,
+                })
+                .collect(),
+        );
+        let spectra = monitors.spectra(&welch).unwrap();
+        assert!((spectra.sampling_frequency - fs).abs() < 1e-9);
+        assert_eq!(spectra.psd.len(), 4);
+        assert_eq!(spectra.csd.len(), 6);
+        assert!(spectra.psd["M1cell_Fx"]
+            .iter()
+            .zip(&psd)
+            .all(|(a, b)| (a - b).abs() < 1e-9));
+    }
+}
@@ -1,12 +1,13 @@
-use flate2::read::GzDecoder;
+use crate::decompress;
 use serde::Deserialize;
-use std::io::prelude::*;
-use std::{fs::File, path::Path};
+use std::path::Path;
 
 #[derive(thiserror::Error, Debug)]
 pub enum TemperatureError {
     #[error("Failed to open the pressure file")]
     Io(#[from] std::io::Error),
+    #[error("Failed to decompress the temperature file")]
+    Decompress(#[from] crate::decompress::DecompressError),
     #[error("Failed to deserialize the CSV file")]
     Csv(#[from] csv::Error),
 }
@@ -41,10 +42,7 @@ impl Default for Temperature {
 }
 impl Temperature {
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let csv_file = File::open(path)?;
-        let mut gz = GzDecoder::new(csv_file);
-        let mut contents = String::new();
-        gz.read_to_string(&mut contents)?;
+        let contents = decompress::read_to_string(path)?;
         let mut rdr = csv::Reader::from_reader(contents.as_bytes());
         let mut this = Self::default();
         for result in rdr.deserialize() {
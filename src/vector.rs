@@ -3,7 +3,7 @@ use std::{
     ops::{Add, AddAssign, Div, Index, IndexMut, Sub},
 };
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct Vector {
     pub x: Option<f64>,
     pub y: Option<f64>,